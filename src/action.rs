@@ -0,0 +1,211 @@
+use super::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(context(suffix(Error)))]
+pub(crate) enum ActionError {
+  #[snafu(display(
+    "action `{action}` expects {} argument(s)",
+    if min == max {
+      min.to_string()
+    } else {
+      format!("{min} to {max}")
+    }
+  ))]
+  Arguments {
+    action: String,
+    max: usize,
+    min: usize,
+  },
+  #[snafu(display("unknown field `{field}`"))]
+  Field { field: String },
+  #[snafu(transparent)]
+  Float { source: num::ParseFloatError },
+  #[snafu(display("unknown program `{program}`"))]
+  Program { program: String },
+  #[snafu(display("unknown action `{action}`"))]
+  Unknown { action: String },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Action {
+  Alpha,
+  Back,
+  Cue,
+  Db,
+  Forward,
+  Horizontal,
+  Jump,
+  Loop,
+  Next,
+  Parameter,
+  Pause,
+  Pop,
+  Previous,
+  Program(Program),
+  Push(Field, Option<String>),
+  ResetX,
+  ResetY,
+  ResetZoom,
+  Restart,
+  Scale(f32),
+  Source(String),
+  Translate(f32, f32),
+  Vertical,
+  Zoom,
+}
+
+impl Action {
+  pub(crate) fn continuous(&self) -> bool {
+    matches!(
+      self,
+      Self::Alpha | Self::Db | Self::Horizontal | Self::Parameter | Self::Vertical | Self::Zoom
+    )
+  }
+}
+
+impl Display for Action {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::Alpha => write!(f, "alpha"),
+      Self::Back => write!(f, "back"),
+      Self::Cue => write!(f, "cue"),
+      Self::Db => write!(f, "db"),
+      Self::Forward => write!(f, "forward"),
+      Self::Horizontal => write!(f, "horizontal"),
+      Self::Jump => write!(f, "jump"),
+      Self::Loop => write!(f, "loop"),
+      Self::Next => write!(f, "next"),
+      Self::Parameter => write!(f, "parameter"),
+      Self::Pause => write!(f, "pause"),
+      Self::Pop => write!(f, "pop"),
+      Self::Previous => write!(f, "previous"),
+      Self::Program(program) => write!(
+        f,
+        "program {}",
+        program.to_possible_value().unwrap().get_name()
+      ),
+      Self::Push(field, source) => {
+        write!(f, "push {}", field.name().to_lowercase())?;
+        if let Some(source) = source {
+          write!(f, " {source}")?;
+        }
+        Ok(())
+      }
+      Self::ResetX => write!(f, "reset-x"),
+      Self::ResetY => write!(f, "reset-y"),
+      Self::ResetZoom => write!(f, "reset-zoom"),
+      Self::Restart => write!(f, "restart"),
+      Self::Scale(scale) => write!(f, "scale {scale}"),
+      Self::Source(source) => write!(f, "source {source}"),
+      Self::Translate(x, y) => write!(f, "translate {x} {y}"),
+      Self::Vertical => write!(f, "vertical"),
+      Self::Zoom => write!(f, "zoom"),
+    }
+  }
+}
+
+impl FromStr for Action {
+  type Err = ActionError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut words = s.split_whitespace();
+
+    let action = words.next().unwrap_or_default();
+
+    let arguments = words.collect::<Vec<&str>>();
+
+    let (min, max) = match action {
+      "program" | "scale" | "source" => (1, 1),
+      "push" => (1, 2),
+      "translate" => (2, 2),
+      _ => (0, 0),
+    };
+
+    if !(min..=max).contains(&arguments.len()) {
+      return Err(ArgumentsError { action, max, min }.build());
+    }
+
+    Ok(match action {
+      "alpha" => Self::Alpha,
+      "back" => Self::Back,
+      "cue" => Self::Cue,
+      "db" => Self::Db,
+      "forward" => Self::Forward,
+      "horizontal" => Self::Horizontal,
+      "jump" => Self::Jump,
+      "loop" => Self::Loop,
+      "next" => Self::Next,
+      "parameter" => Self::Parameter,
+      "pause" => Self::Pause,
+      "pop" => Self::Pop,
+      "previous" => Self::Previous,
+      "program" => Self::Program(Program::from_str(arguments[0], false).ok().context(
+        ProgramError {
+          program: arguments[0],
+        },
+      )?),
+      "push" => Self::Push(
+        Field::iter()
+          .find(|field| field.name().eq_ignore_ascii_case(arguments[0]))
+          .context(FieldError {
+            field: arguments[0],
+          })?,
+        arguments.get(1).map(|source| (*source).into()),
+      ),
+      "reset-x" => Self::ResetX,
+      "reset-y" => Self::ResetY,
+      "reset-zoom" => Self::ResetZoom,
+      "restart" => Self::Restart,
+      "scale" => Self::Scale(arguments[0].parse()?),
+      "source" => Self::Source(arguments[0].into()),
+      "translate" => Self::Translate(arguments[0].parse()?, arguments[1].parse()?),
+      "vertical" => Self::Vertical,
+      "zoom" => Self::Zoom,
+      _ => return Err(UnknownError { action }.build()),
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn round_trip() {
+    for action in [
+      Action::Db,
+      Action::Program(Program::Highwaystar),
+      Action::Push(Field::Circle, None),
+      Action::Push(Field::Beat, Some("drums".into())),
+      Action::Source("vocals".into()),
+      Action::ResetZoom,
+      Action::Scale(0.5),
+      Action::Translate(-0.1, 0.0),
+    ] {
+      assert_eq!(action.to_string().parse::<Action>().unwrap(), action);
+    }
+
+    assert!(matches!(
+      "push".parse::<Action>(),
+      Err(ActionError::Arguments { .. })
+    ));
+
+    assert_eq!(
+      "push circle drums bass"
+        .parse::<Action>()
+        .unwrap_err()
+        .to_string(),
+      "action `push` expects 1 to 2 argument(s)",
+    );
+
+    assert!(matches!(
+      "push square".parse::<Action>(),
+      Err(ActionError::Field { .. })
+    ));
+
+    assert!(matches!(
+      "explode".parse::<Action>(),
+      Err(ActionError::Unknown { .. })
+    ));
+  }
+}
@@ -0,0 +1,463 @@
+use super::*;
+
+pub(crate) struct Analysis {
+  fps: f32,
+  frames: Vec<Snapshot>,
+  sections: Vec<(usize, Section)>,
+}
+
+impl Analysis {
+  const MAGIC: &[u8; 4] = b"XANL";
+  const MAX_BPM: f32 = 180.0;
+  const MIN_BPM: f32 = 60.0;
+  const MIN_SECTION: f32 = 8.0;
+  const NOVELTY_KERNEL: f32 = 4.0;
+  const SEGMENT: f32 = 0.5;
+  const VERSION: u32 = 2;
+
+  fn beats(frames: &mut [Snapshot], fps: f32) {
+    let mean = frames.iter().map(|frame| frame.onset).sum::<f32>() / frames.len().max(1) as f32;
+
+    let onsets = frames
+      .iter()
+      .map(|frame| (frame.onset - mean).max(0.0))
+      .collect::<Vec<f32>>();
+
+    let min_lag = (fps * 60.0 / Self::MAX_BPM).round().into_usize().max(1);
+    let max_lag = (fps * 60.0 / Self::MIN_BPM).round().into_usize();
+
+    let autocorrelation = |lag: usize| {
+      onsets
+        .iter()
+        .zip(onsets.iter().skip(lag))
+        .map(|(a, b)| a * b)
+        .sum::<f32>()
+        / onsets.len().saturating_sub(lag).max(1) as f32
+    };
+
+    let Some((lag, _)) = (min_lag..=max_lag.min(onsets.len() / 2))
+      .map(|lag| (lag, autocorrelation(lag)))
+      .max_by(|a, b| a.1.total_cmp(&b.1))
+    else {
+      return;
+    };
+
+    // refine the period with parabolic interpolation around the peak
+    let (before, peak, after) = (
+      autocorrelation(lag - 1),
+      autocorrelation(lag),
+      autocorrelation(lag + 1),
+    );
+    let denominator = before - 2.0 * peak + after;
+    let period = if denominator < 0.0 {
+      lag as f32 + 0.5 * (before - after) / denominator
+    } else {
+      lag as f32
+    };
+
+    let grid = |phase: usize| {
+      (0..)
+        .map(move |k| (phase as f32 + k as f32 * period).round().into_usize())
+        .take_while(|&i| i < onsets.len())
+    };
+
+    let Some(phase) = (0..lag).max_by(|&a, &b| {
+      let score = |phase| grid(phase).map(|i| onsets[i]).sum::<f32>();
+      score(a).total_cmp(&score(b))
+    }) else {
+      return;
+    };
+
+    for i in grid(phase) {
+      frames[i].beat = true;
+    }
+  }
+
+  pub(crate) fn compute(
+    samples: Vec<f32>,
+    channels: u16,
+    sample_rate: u32,
+    options: &Options,
+  ) -> Self {
+    let mut analyzer = Analyzer::new(options);
+
+    let state = State::default();
+
+    let hop = analyzer.hop() * usize::from(channels);
+
+    let mut frames = Vec::new();
+
+    for chunk in samples.chunks_exact(hop) {
+      analyzer.update(
+        &mut Samples::new(channels, sample_rate, chunk.into()),
+        &state,
+      );
+      frames.push(analyzer.snapshot());
+    }
+
+    let fps = sample_rate as f32 / analyzer.hop() as f32;
+
+    Self::beats(&mut frames, fps);
+
+    Self::new(fps, frames)
+  }
+
+  fn decode(bytes: &[u8]) -> Option<Self> {
+    let bytes = bytes.strip_prefix(Self::MAGIC)?;
+
+    let (version, bytes) = bytes.split_first_chunk::<4>()?;
+
+    if u32::from_le_bytes(*version) != Self::VERSION {
+      return None;
+    }
+
+    let (fps, bytes) = bytes.split_first_chunk::<4>()?;
+
+    if bytes.len() % (Snapshot::LEN * 4) != 0 {
+      return None;
+    }
+
+    let values = bytes
+      .chunks_exact(4)
+      .map(|chunk| f32::from_le_bytes(chunk.try_into().unwrap()))
+      .collect::<Vec<f32>>();
+
+    Some(Self::new(
+      f32::from_le_bytes(*fps),
+      values
+        .chunks_exact(Snapshot::LEN)
+        .map(Snapshot::from_values)
+        .collect(),
+    ))
+  }
+
+  fn encode(&self) -> Vec<u8> {
+    let mut bytes = Self::MAGIC.to_vec();
+    bytes.extend(Self::VERSION.to_le_bytes());
+    bytes.extend(self.fps.to_le_bytes());
+    for snapshot in &self.frames {
+      for value in snapshot.values() {
+        bytes.extend(value.to_le_bytes());
+      }
+    }
+    bytes
+  }
+
+  pub(crate) fn get(&self, frame: usize) -> Option<&Snapshot> {
+    self.frames.get(frame)
+  }
+
+  fn hash(bytes: &[u8], options: &Options) -> u64 {
+    let key = format!(
+      "{:?} {:?} {:?} {:?} {}",
+      options.fft_size,
+      options.hop,
+      options.max_frequency,
+      options.min_frequency,
+      options.window_function.unwrap_or_default() as u8,
+    );
+
+    // 64-bit FNV-1a
+    bytes
+      .iter()
+      .chain(key.as_bytes())
+      .fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+      })
+  }
+
+  pub(crate) fn load(path: &Path, options: &Options) -> Result<Self> {
+    let bytes = fs::read(path).context(error::FilesystemIo { path })?;
+
+    let cache = dirs::cache_dir().map(|dir| {
+      dir
+        .join("x")
+        .join(format!("{:016x}", Self::hash(&bytes, options)))
+    });
+
+    if let Some(cache) = &cache
+      && let Ok(cached) = fs::read(cache)
+      && let Some(analysis) = Self::decode(&cached)
+    {
+      log::info!("loaded analysis from {}", cache.display());
+      return Ok(analysis);
+    }
+
+    let decoder = Decoder::new(io::Cursor::new(bytes)).context(error::DecoderOpen { path })?;
+
+    let channels = decoder.channels();
+    let sample_rate = decoder.sample_rate();
+
+    let analysis = Self::compute(decoder.collect(), channels, sample_rate, options);
+
+    let Some(cache) = cache else {
+      log::warn!("could not get cache directory, analysis will not be cached");
+      return Ok(analysis);
+    };
+
+    match fs::create_dir_all(cache.parent().unwrap())
+      .and_then(|()| fs::write(&cache, analysis.encode()))
+    {
+      Ok(()) => log::info!("saved analysis to {}", cache.display()),
+      Err(err) => log::warn!("failed to save analysis to {}: {err}", cache.display()),
+    }
+
+    Ok(analysis)
+  }
+
+  fn new(fps: f32, frames: Vec<Snapshot>) -> Self {
+    Self {
+      sections: Self::sections(&frames, fps),
+      fps,
+      frames,
+    }
+  }
+
+  pub(crate) fn section(&self, frame: usize) -> Option<(usize, Section)> {
+    self
+      .sections
+      .iter()
+      .rev()
+      .find(|(start, _)| *start <= frame)
+      .copied()
+  }
+
+  fn sections(frames: &[Snapshot], fps: f32) -> Vec<(usize, Section)> {
+    let segment = (fps * Self::SEGMENT).round().into_usize().max(1);
+
+    // average compressed band and rms energies over each segment
+    let segments = frames
+      .chunks(segment)
+      .map(|chunk| {
+        let mut vector = [0.0; Band::COUNT + 1];
+        for snapshot in chunk {
+          for (value, energy) in vector
+            .iter_mut()
+            .zip(snapshot.bands.iter().chain([&snapshot.rms]))
+          {
+            *value += (1.0 + 1000.0 * energy).ln() / chunk.len() as f32;
+          }
+        }
+        vector
+      })
+      .collect::<Vec<_>>();
+
+    let similarity = |a: usize, b: usize| {
+      let (a, b) = (&segments[a], &segments[b]);
+      let dot = a.iter().zip(b).map(|(a, b)| a * b).sum::<f32>();
+      let norm =
+        a.iter().map(|a| a * a).sum::<f32>().sqrt() * b.iter().map(|b| b * b).sum::<f32>().sqrt();
+      if norm > 0.0 {
+        dot / norm
+      } else if a == b {
+        1.0
+      } else {
+        0.0
+      }
+    };
+
+    // novelty from a checkerboard kernel sliding along the self-similarity diagonal
+    let kernel = (Self::NOVELTY_KERNEL / Self::SEGMENT).round().into_usize();
+
+    let mut novelty = vec![0.0; segments.len()];
+
+    for (i, novelty) in novelty
+      .iter_mut()
+      .enumerate()
+      .take(segments.len().saturating_sub(kernel))
+      .skip(kernel)
+    {
+      for a in i - kernel..i + kernel {
+        for b in i - kernel..i + kernel {
+          let sign = if (a < i) == (b < i) { 1.0 } else { -1.0 };
+          *novelty += sign * similarity(a, b);
+        }
+      }
+    }
+
+    let mean = novelty.iter().sum::<f32>() / novelty.len().max(1) as f32;
+    let deviation = (novelty.iter().map(|n| (n - mean).powi(2)).sum::<f32>()
+      / novelty.len().max(1) as f32)
+      .sqrt();
+    let threshold = mean + 0.5 * deviation;
+
+    let spacing = (Self::MIN_SECTION / Self::SEGMENT).round().into_usize();
+
+    let mut boundaries = vec![0];
+
+    let mut candidates = (1..novelty.len().saturating_sub(1))
+      .filter(|&i| {
+        novelty[i] > threshold && novelty[i] >= novelty[i - 1] && novelty[i] >= novelty[i + 1]
+      })
+      .collect::<Vec<usize>>();
+
+    candidates.sort_by(|&a, &b| novelty[b].total_cmp(&novelty[a]));
+
+    for candidate in candidates {
+      if boundaries
+        .iter()
+        .all(|&boundary: &usize| boundary.abs_diff(candidate) >= spacing)
+        && segments.len() - candidate >= spacing
+      {
+        boundaries.push(candidate);
+      }
+    }
+
+    boundaries.sort_unstable();
+
+    let energies = boundaries
+      .iter()
+      .enumerate()
+      .map(|(i, &start)| {
+        let end = boundaries.get(i + 1).copied().unwrap_or(segments.len());
+        frames[start * segment..(end * segment).min(frames.len())]
+          .iter()
+          .map(|snapshot| snapshot.rms)
+          .sum::<f32>()
+          / ((end - start) * segment).max(1) as f32
+      })
+      .collect::<Vec<f32>>();
+
+    let mut sorted = energies.clone();
+    sorted.sort_by(f32::total_cmp);
+    let median = sorted.get(sorted.len() / 2).copied().unwrap_or_default();
+
+    boundaries
+      .iter()
+      .enumerate()
+      .map(|(i, &start)| {
+        let energy = energies[i];
+        let section = if i == 0 && energy <= median && boundaries.len() > 1 {
+          Section::Intro
+        } else if i == boundaries.len() - 1 && energy < median && boundaries.len() > 2 {
+          Section::Outro
+        } else if energy > median * 1.2 {
+          if i > 0 && energies[i - 1] < energy * 0.6 {
+            Section::Drop
+          } else {
+            Section::Chorus
+          }
+        } else {
+          Section::Verse
+        };
+        (start * segment, section)
+      })
+      .collect()
+  }
+
+  pub(crate) fn upcoming(&self, frame: usize, frames: usize) -> f32 {
+    self
+      .frames
+      .iter()
+      .skip(frame + 1)
+      .take(frames)
+      .map(|snapshot| snapshot.rms)
+      .fold(0.0, f32::max)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn beats() {
+    let samples = Click::new().take(48_000 * 10).collect::<Vec<f32>>();
+
+    let analysis = Analysis::compute(samples, 1, 48_000, &Options::default());
+
+    let beats = analysis
+      .frames
+      .iter()
+      .enumerate()
+      .filter(|(_, snapshot)| snapshot.beat)
+      .map(|(i, _)| i as f32 * 512.0 / 48_000.0)
+      .collect::<Vec<f32>>();
+
+    assert!((19..=21).contains(&beats.len()), "beats: {}", beats.len());
+
+    for beat in beats {
+      let offset = (beat + 0.25).rem_euclid(0.5) - 0.25;
+      assert!(offset.abs() < 0.05, "offset: {offset}");
+    }
+  }
+
+  #[test]
+  fn sections() {
+    let fps = 10.0;
+
+    let section = |seconds: usize, bands: [f32; Band::COUNT], rms: f32| {
+      vec![
+        Snapshot {
+          bands,
+          rms,
+          ..default()
+        };
+        seconds * 10
+      ]
+    };
+
+    let frames = [
+      section(20, [0.01, 0.02, 0.0, 0.0, 0.0], 0.02),
+      section(20, [0.1, 0.1, 0.05, 0.05, 0.0], 0.15),
+      section(20, [0.5, 0.5, 0.3, 0.4, 0.3], 0.9),
+      section(20, [0.01, 0.0, 0.0, 0.0, 0.02], 0.02),
+    ]
+    .concat();
+
+    let analysis = Analysis::new(fps, frames);
+
+    assert_eq!(
+      analysis.sections,
+      [
+        (0, Section::Intro),
+        (200, Section::Verse),
+        (400, Section::Drop),
+        (600, Section::Outro),
+      ],
+    );
+
+    assert_eq!(analysis.section(0), Some((0, Section::Intro)));
+    assert_eq!(analysis.section(399), Some((200, Section::Verse)));
+    assert_eq!(analysis.section(400), Some((400, Section::Drop)));
+    assert_eq!(analysis.section(401), Some((400, Section::Drop)));
+    assert_eq!(analysis.section(10_000), Some((600, Section::Outro)));
+  }
+
+  #[test]
+  fn round_trip() {
+    let analysis = Analysis::new(
+      93.75,
+      vec![
+        Snapshot {
+          bands: [1.0, 2.0, 3.0, 4.0, 5.0],
+          beat: true,
+          onset: 6.0,
+          rms: 7.0,
+        },
+        Snapshot::default(),
+      ],
+    );
+
+    let decoded = Analysis::decode(&analysis.encode()).unwrap();
+
+    assert_eq!(decoded.fps, analysis.fps);
+
+    assert_eq!(
+      decoded
+        .frames
+        .iter()
+        .flat_map(|snapshot| snapshot.values())
+        .collect::<Vec<f32>>(),
+      analysis
+        .frames
+        .iter()
+        .flat_map(|snapshot| snapshot.values())
+        .collect::<Vec<f32>>(),
+    );
+
+    assert!(Analysis::decode(b"XANL").is_none());
+    assert!(Analysis::decode(b"XANL\x01\0\0\0").is_none());
+    assert!(Analysis::decode(b"nope").is_none());
+  }
+}
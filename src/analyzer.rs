@@ -1,28 +1,359 @@
 use super::*;
 
 pub(crate) struct Analyzer {
-  complex_frequencies: Vec<Complex<f32>>,
+  analysis: Option<Analysis>,
+  bands: [Envelope; Band::COUNT],
+  beat: Envelope,
+  bins: usize,
+  chroma: Chroma,
+  correlation: f32,
+  envelope: Envelope,
+  envelopes: Vec<Envelope>,
+  fft: Arc<dyn Fft<f32>>,
+  floor: Option<f32>,
+  frame: usize,
   frequencies: Vec<f32>,
-  planner: FftPlanner<f32>,
+  hop: usize,
+  left: Channel,
+  levels: Vec<f32>,
+  loudness: Loudness,
+  max_frequency: f32,
+  meters: Vec<Peak>,
+  mid: Channel,
+  min_frequency: f32,
+  mono: Channel,
+  onset: Envelope,
+  partial: Vec<f32>,
+  peak_decay: f32,
+  peak_hold: f32,
+  peaks: Vec<f32>,
+  pending: usize,
+  previous: Vec<f32>,
+  right: Channel,
   rms: f32,
-  samples: Vec<f32>,
+  scale: Scale,
   scratch: Vec<Complex<f32>>,
+  section: Option<(usize, Section)>,
+  sections: Vec<Section>,
+  side: Channel,
+  snapshot: Snapshot,
+  spectrogram: History,
+  upcoming: f32,
+  waveform: History,
+  window: Vec<f32>,
 }
 
 impl Analyzer {
+  fn analyze(&mut self, sample_rate: f32, state: &State) {
+    for channel in [
+      &mut self.left,
+      &mut self.mid,
+      &mut self.mono,
+      &mut self.right,
+      &mut self.side,
+    ] {
+      channel.analyze(self.fft.as_ref(), &self.window, &mut self.scratch);
+    }
+
+    let (lr, ll, rr) = self
+      .left
+      .samples()
+      .iter()
+      .zip(self.right.samples())
+      .fold((0.0, 0.0, 0.0), |(lr, ll, rr), (l, r)| {
+        (lr + l * r, ll + l * l, rr + r * r)
+      });
+
+    self.correlation = if ll * rr > 0.0 {
+      lr / (ll * rr).sqrt()
+    } else {
+      0.0
+    };
+
+    let size = self.window.len();
+    let half = size / 2;
+    let spectrum = self.mono.spectrum();
+
+    let spacing = sample_rate / size as f32;
+    let threshold = (self.min_frequency / spacing).into_usize();
+    let cutoff = (self.max_frequency / spacing).into_usize().min(half);
+    let range = &spectrum[threshold.min(cutoff)..cutoff];
+
+    self.levels.clear();
+
+    if let Scale::Linear = self.scale {
+      self.levels.extend_from_slice(range);
+    } else {
+      let min = self.scale.to_scale(self.min_frequency);
+      let max = self.scale.to_scale(self.max_frequency);
+      let edge = |i: usize| {
+        self
+          .scale
+          .to_hz(min + (max - min) * i as f32 / self.bins as f32)
+      };
+
+      for i in 0..self.bins {
+        let low = edge(i);
+        let high = edge(i + 1);
+        let start = (low / spacing).ceil().into_usize().min(half);
+        let end = (high / spacing).ceil().into_usize().min(half + 1);
+
+        self.levels.push(if start < end {
+          spectrum[start..end].iter().copied().fold(0.0, f32::max)
+        } else {
+          let x = f32::midpoint(low, high) / spacing;
+          let i = x.into_usize().min(half);
+          let t = x.fract();
+          spectrum[i] * (1.0 - t) + spectrum[(i + 1).min(half)] * t
+        });
+      }
+    }
+
+    if let Some(floor) = self.floor {
+      for level in &mut self.levels {
+        *level = (1.0 - 20.0 * level.log10() / floor).clamp(0.0, 1.0);
+      }
+    }
+
+    let elapsed = self.hop as f32 / sample_rate;
+
+    self.envelopes.resize(self.levels.len(), self.envelope);
+    self.meters.resize(self.levels.len(), Peak::default());
+    self.frequencies.clear();
+    self.peaks.clear();
+
+    for ((&level, envelope), meter) in self
+      .levels
+      .iter()
+      .zip(&mut self.envelopes)
+      .zip(&mut self.meters)
+    {
+      envelope.update(level, elapsed);
+      meter.update(level, elapsed, self.peak_hold, self.peak_decay);
+      self.frequencies.push(envelope.value());
+      self.peaks.push(meter.value());
+    }
+
+    self.spectrogram.push(self.frequencies.iter().copied());
+
+    let samples = self.mono.samples();
+    let recent = &samples[samples.len().saturating_sub(self.hop)..];
+    self.waveform.push([
+      recent.iter().copied().fold(0.0, f32::min),
+      recent.iter().copied().fold(0.0, f32::max),
+    ]);
+
+    let mut bands = [0.0; Band::COUNT];
+
+    for (band, value) in Band::iter().zip(&mut bands) {
+      let (low, high) = band.range();
+      let start = (low / spacing).ceil().into_usize().max(1);
+      let end = ((high / spacing).into_usize() + 1).min(half);
+      *value = energy(&spectrum[start.min(end)..end]);
+    }
+
+    let onset = spectrum
+      .iter()
+      .zip(&self.previous)
+      .map(|(magnitude, previous)| (magnitude - previous).max(0.0))
+      .sum();
+
+    self.snapshot = Snapshot {
+      bands,
+      beat: false,
+      onset,
+      rms: (samples.iter().map(|sample| sample * sample).sum::<f32>()
+        / samples.len().max(1) as f32)
+        .sqrt(),
+    };
+
+    self.chroma.update(spectrum, spacing, elapsed);
+
+    self.previous.clear();
+    self.previous.extend_from_slice(self.mono.spectrum());
+
+    if let Some(analysis) = &self.analysis {
+      if let Some(snapshot) = analysis.get(self.frame) {
+        self.snapshot = *snapshot;
+      }
+
+      let section = analysis.section(self.frame);
+
+      if section != self.section {
+        self.section = section;
+        self.sections.extend(section.map(|(_, section)| section));
+      }
+
+      self.upcoming = analysis.upcoming(self.frame, (0.5 / elapsed).into_usize());
+    }
+
+    self.frame += 1;
+
+    for (envelope, energy) in self.bands.iter_mut().zip(self.snapshot.bands) {
+      envelope.update(energy, elapsed);
+    }
+
+    self
+      .beat
+      .update(if self.snapshot.beat { 1.0 } else { 0.0 }, elapsed);
+
+    self.onset.update(self.snapshot.onset, elapsed);
+
+    let alpha = state.alpha.unipolar();
+    self.rms = alpha * self.snapshot.rms + (1.0 - alpha) * self.rms;
+  }
+
+  pub(crate) fn bands(&self) -> [f32; Band::COUNT] {
+    self.bands.map(Envelope::value)
+  }
+
+  pub(crate) fn beat(&self) -> f32 {
+    self.beat.value()
+  }
+
+  pub(crate) fn chroma(&self) -> [f32; Chroma::PITCH_CLASSES] {
+    self.chroma.values()
+  }
+
+  pub(crate) fn correlation(&self) -> f32 {
+    self.correlation
+  }
+
   pub(crate) fn frequencies(&self) -> &[f32] {
     &self.frequencies
   }
 
-  pub(crate) fn new() -> Self {
+  pub(crate) fn hop(&self) -> usize {
+    self.hop
+  }
+
+  pub(crate) fn left(&self) -> &Channel {
+    &self.left
+  }
+
+  pub(crate) fn loudness(&self) -> &Loudness {
+    &self.loudness
+  }
+
+  pub(crate) fn mid(&self) -> &Channel {
+    &self.mid
+  }
+
+  pub(crate) fn mono(&self) -> &Channel {
+    &self.mono
+  }
+
+  pub(crate) fn new(options: &Options) -> Self {
+    let size = options.fft_size.unwrap_or(2048).into_usize();
+
+    let fft = FftPlanner::new().plan_fft_forward(size);
+
+    let envelope = Envelope::new(
+      options.spectrum_attack.unwrap_or(0.0),
+      options.spectrum_release.unwrap_or(0.15),
+    );
+
     Self {
-      complex_frequencies: Vec::new(),
+      analysis: None,
+      bands: array::from_fn(|i| {
+        let band = Band::iter().nth(i).unwrap();
+        Envelope::new(
+          options.band_attack.unwrap_or(band.attack()),
+          options.band_release.unwrap_or(band.release()),
+        )
+      }),
+      beat: Envelope::new(0.0, 0.1),
+      bins: options.spectrum_bins.unwrap_or(256).into_usize(),
+      chroma: Chroma::new(envelope),
+      correlation: 0.0,
+      envelope,
+      envelopes: Vec::new(),
+      floor: options.spectrum_db.then(|| options.spectrum_floor()),
+      frame: 0,
       frequencies: Vec::new(),
-      planner: FftPlanner::new(),
+      hop: options.hop.map_or(size / 4, IntoUsize::into_usize).max(1),
+      left: Channel::default(),
+      levels: Vec::new(),
+      loudness: Loudness::default(),
+      max_frequency: options.max_frequency(),
+      meters: Vec::new(),
+      mid: Channel::default(),
+      min_frequency: options.min_frequency(),
+      mono: Channel::default(),
+      onset: Envelope::new(0.0, 0.1),
+      partial: Vec::new(),
+      peak_decay: options.peak_decay.unwrap_or(0.5),
+      peak_hold: options.peak_hold.unwrap_or(0.5),
+      peaks: Vec::new(),
+      pending: 0,
+      previous: Vec::new(),
+      right: Channel::default(),
       rms: 0.0,
-      samples: Vec::new(),
-      scratch: Vec::new(),
+      scale: options.spectrum_scale.unwrap_or_default(),
+      scratch: vec![Complex::default(); fft.get_inplace_scratch_len()],
+      section: None,
+      sections: Vec::new(),
+      side: Channel::default(),
+      snapshot: Snapshot::default(),
+      spectrogram: History::default(),
+      upcoming: 0.0,
+      waveform: History::default(),
+      window: options
+        .window_function
+        .unwrap_or_default()
+        .coefficients(size),
+      fft,
+    }
+  }
+
+  pub(crate) fn onset(&self) -> f32 {
+    self.onset.value()
+  }
+
+  pub(crate) fn peaks(&self) -> &[f32] {
+    &self.peaks
+  }
+
+  pub(crate) fn pitch(&self) -> Option<u32> {
+    self.chroma.pitch()
+  }
+
+  pub(crate) fn reset(&mut self) {
+    self.beat.reset();
+    self.correlation = 0.0;
+    self.envelopes.clear();
+    self.frame = 0;
+    self.frequencies.clear();
+    self.levels.clear();
+    self.loudness.reset();
+    self.onset.reset();
+    self.meters.clear();
+    self.partial.clear();
+    self.peaks.clear();
+    self.pending = 0;
+    self.previous.clear();
+    self.rms = 0.0;
+    self.section = None;
+    self.sections.clear();
+    self.snapshot = Snapshot::default();
+    self.upcoming = 0.0;
+    for channel in [
+      &mut self.left,
+      &mut self.mid,
+      &mut self.mono,
+      &mut self.right,
+      &mut self.side,
+    ] {
+      channel.clear();
+    }
+    for envelope in &mut self.bands {
+      envelope.reset();
     }
+    self.chroma.reset();
+  }
+
+  pub(crate) fn right(&self) -> &Channel {
+    &self.right
   }
 
   pub(crate) fn rms(&self) -> f32 {
@@ -30,67 +361,272 @@ impl Analyzer {
   }
 
   pub(crate) fn samples(&self) -> &[f32] {
-    &self.samples
+    self.mono.samples()
+  }
+
+  pub(crate) fn section(&self) -> Option<Section> {
+    self.section.map(|(_, section)| section)
+  }
+
+  pub(crate) fn set_analysis(&mut self, analysis: Analysis) {
+    self.analysis = Some(analysis);
+  }
+
+  pub(crate) fn side(&self) -> &Channel {
+    &self.side
+  }
+
+  pub(crate) fn snapshot(&self) -> Snapshot {
+    self.snapshot
+  }
+
+  pub(crate) fn spectrogram(&self) -> &History {
+    &self.spectrogram
+  }
+
+  pub(crate) fn take_sections(&mut self) -> Vec<Section> {
+    mem::take(&mut self.sections)
+  }
+
+  pub(crate) fn upcoming(&self) -> f32 {
+    self.upcoming
   }
 
   pub(crate) fn update(&mut self, stream: &mut dyn Stream, state: &State) {
     if stream.done() {
-      self.samples.clear();
-    } else {
-      let mut samples = Vec::new();
-      stream.drain(&mut samples);
-      let old = self.samples.len();
-      let channels = stream.channels();
-      self.samples.extend(
-        samples
-          .chunks(channels.into())
-          .map(|chunk| chunk.iter().sum::<f32>() / channels as f32),
+      self.reset();
+      return;
+    }
+
+    if let Some(position) = stream.take_seek() {
+      self.reset();
+      self.frame = position / self.hop;
+      self.pending = position % self.hop;
+    }
+
+    let channels = usize::from(stream.channels());
+    let sample_rate = stream.sample_rate();
+    let size = self.window.len();
+
+    let mut samples = mem::take(&mut self.partial);
+
+    stream.drain(&mut samples);
+
+    let complete = samples.len() / channels * channels;
+
+    let mut remaining = &samples[..complete];
+
+    while !remaining.is_empty() {
+      let n = (self.hop - self.pending).min(remaining.len() / channels);
+      let chunk = &remaining[..n * channels];
+      remaining = &remaining[n * channels..];
+
+      let frames = || chunk.chunks(channels);
+      let left = |frame: &[f32]| frame[0];
+      let right = |frame: &[f32]| frame.get(1).copied().unwrap_or(frame[0]);
+
+      self.left.extend(frames().map(left), size);
+      self.mid.extend(
+        frames().map(|frame| f32::midpoint(left(frame), right(frame))),
+        size,
       );
-      self
-        .samples
-        .drain(..self.samples.len().saturating_sub(128).min(old));
+      self.mono.extend(
+        frames().map(|frame| frame.iter().sum::<f32>() / channels as f32),
+        size,
+      );
+      self.right.extend(frames().map(right), size);
+      self.side.extend(
+        frames().map(|frame| (left(frame) - right(frame)) / 2.0),
+        size,
+      );
+
+      self.loudness.update(chunk, channels, sample_rate);
+
+      self.pending += n;
+
+      if self.pending == self.hop {
+        self.pending = 0;
+        self.analyze(sample_rate as f32, state);
+      }
     }
 
-    let samples = &self.samples[..self.samples.len() & !1];
+    samples.drain(..complete);
 
-    self.complex_frequencies.clear();
-    self
-      .complex_frequencies
-      .extend(samples.iter().map(Complex::from));
-    let fft = self.planner.plan_fft_forward(samples.len());
-    let scratch_len = fft.get_inplace_scratch_len();
-    if self.scratch.len() < scratch_len {
-      self.scratch.resize(scratch_len, 0.0.into());
-    }
-    fft.process_with_scratch(
-      &mut self.complex_frequencies,
-      &mut self.scratch[..scratch_len],
-    );
+    self.partial = samples;
+  }
 
-    let n = self.complex_frequencies.len();
-    let half = n / 2;
-    let spacing = stream.sample_rate() as f32 / n as f32;
-    let threshold = (20.0 / spacing).into_usize();
-    let cutoff = (15_000.0 / spacing).into_usize();
+  pub(crate) fn waveform(&self) -> &History {
+    &self.waveform
+  }
+}
 
-    self.frequencies.clear();
-    self.frequencies.extend(
-      self
-        .complex_frequencies
+fn energy(spectrum: &[f32]) -> f32 {
+  spectrum.iter().map(|m| m * m).sum::<f32>().sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const SAMPLE_RATE: u32 = 48_000;
+
+  fn mono(samples: Vec<f32>) -> Samples {
+    Samples::new(1, SAMPLE_RATE, samples)
+  }
+
+  fn peak(spectrum: &[f32]) -> usize {
+    spectrum
+      .iter()
+      .enumerate()
+      .max_by(|a, b| a.1.total_cmp(b.1))
+      .unwrap()
+      .0
+  }
+
+  fn sine(frequency: f32, len: usize) -> Vec<f32> {
+    (0..len)
+      .map(|i| (TAU * frequency * i as f32 / SAMPLE_RATE as f32).sin())
+      .collect()
+  }
+
+  #[test]
+  fn chroma() {
+    let mut analyzer = Analyzer::new(&Options::default());
+
+    assert_eq!(analyzer.pitch(), None);
+
+    analyzer.update(&mut mono(sine(440.0, 8192)), &State::default());
+
+    assert_eq!(analyzer.pitch(), Some(9));
+    assert!((analyzer.chroma()[9] - 1.0).abs() < 0.001);
+  }
+
+  #[test]
+  fn log_scale() {
+    let mut analyzer = Analyzer::new(&Options {
+      max_frequency: Some(20_000.0),
+      min_frequency: Some(20.0),
+      spectrum_bins: Some(100),
+      spectrum_db: true,
+      spectrum_scale: Some(Scale::Log),
+      ..default()
+    });
+
+    analyzer.update(&mut mono(sine(1031.25, 8192)), &State::default());
+
+    assert_eq!(analyzer.levels.len(), 100);
+
+    let peak = peak(&analyzer.levels);
+
+    assert_eq!(peak, 57);
+    assert!(analyzer.levels[peak] > 0.99);
+    assert!(analyzer.levels[10] < 0.01);
+  }
+
+  #[test]
+  fn peak_bin() {
+    #[track_caller]
+    fn case(fft_size: u32, window_function: WindowFunction, frequency: f32) {
+      let mut analyzer = Analyzer::new(&Options {
+        fft_size: Some(fft_size),
+        window_function: Some(window_function),
+        ..default()
+      });
+
+      analyzer.update(
+        &mut mono(sine(frequency, fft_size.into_usize() * 4)),
+        &State::default(),
+      );
+
+      let spacing = SAMPLE_RATE as f32 / fft_size as f32;
+
+      let spectrum = analyzer.mono.spectrum();
+
+      let peak = peak(spectrum);
+
+      assert_eq!(peak, (frequency / spacing).round().into_usize());
+
+      let magnitude = spectrum[peak];
+      assert!((magnitude - 1.0).abs() < 0.01, "magnitude: {magnitude}");
+
+      let rms = analyzer.snapshot.rms;
+      assert!((rms - 0.5f32.sqrt()).abs() < 0.01, "rms: {rms}");
+    }
+
+    case(1024, WindowFunction::Hann, 375.0);
+    case(2048, WindowFunction::Hann, 1500.0);
+    case(2048, WindowFunction::Blackman, 93.75);
+    case(4096, WindowFunction::Rectangular, 11_718.75);
+  }
+
+  #[test]
+  fn stereo() {
+    #[track_caller]
+    fn case(right: f32, correlation: f32, mid: f32, side: f32) {
+      let mut analyzer = Analyzer::new(&Options {
+        fft_size: Some(1024),
+        ..default()
+      });
+
+      let left = sine(375.0, 4096);
+
+      let right = left
         .iter()
-        .enumerate()
-        .skip(threshold)
-        .take(cutoff.min(half).saturating_sub(threshold))
-        .map(|(i, c)| {
-          let weight = if i == 0 || i == half { 1.0 } else { 2.0 };
-          c.norm() * weight
-        }),
-    );
-    let alpha = state.alpha.unipolar();
-    self.rms = alpha
-      * (self.frequencies.iter().map(|&f| f * f).sum::<f32>()
-        / self.frequencies.len().max(1) as f32)
-        .sqrt()
-      + (1.0 - alpha) * self.rms;
+        .map(|sample| sample * right)
+        .collect::<Vec<f32>>();
+
+      let samples = left
+        .iter()
+        .zip(&right)
+        .flat_map(|(&l, &r)| [l, r])
+        .collect::<Vec<f32>>();
+
+      for chunk in samples.chunks(123) {
+        analyzer.update(
+          &mut Samples::new(2, SAMPLE_RATE, chunk.into()),
+          &State::default(),
+        );
+      }
+
+      assert!((analyzer.correlation() - correlation).abs() < 0.001);
+
+      for (channel, expected) in [
+        (analyzer.left(), 1.0),
+        (analyzer.mid(), mid),
+        (analyzer.side(), side),
+      ] {
+        let magnitude = channel.spectrum()[8];
+        assert!(
+          (magnitude - expected).abs() < 0.001,
+          "magnitude: {magnitude} expected: {expected}",
+        );
+      }
+    }
+
+    case(1.0, 1.0, 1.0, 0.0);
+    case(-1.0, -1.0, 0.0, 1.0);
+    case(0.0, 0.0, 0.5, 0.5);
+  }
+
+  #[test]
+  fn update_is_independent_of_chunk_size() {
+    let options = Options {
+      fft_size: Some(1024),
+      hop: Some(256),
+      ..default()
+    };
+
+    let state = State::default();
+
+    let mut whole = Analyzer::new(&options);
+    whole.update(&mut mono(sine(440.0, 10_000)), &state);
+
+    let mut chunked = Analyzer::new(&options);
+    for chunk in sine(440.0, 10_000).chunks(123) {
+      chunked.update(&mut mono(chunk.into()), &state);
+    }
+
+    assert_eq!(whole.mono.samples(), chunked.mono.samples());
+    assert_eq!(whole.mono.spectrum(), chunked.mono.spectrum());
   }
 }
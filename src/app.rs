@@ -2,22 +2,32 @@ use super::*;
 
 pub(crate) struct App {
   analyzer: Analyzer,
+  autopilot: Autopilot,
+  band: Option<Band>,
   error: Option<Error>,
+  fading: Option<(Sink, Instant)>,
   horizontal: f32,
   hub: Hub,
+  lookahead: Duration,
   makro: Vec<Key>,
+  mapping: Mapping,
   options: Options,
-  #[allow(unused)]
-  output_stream: OutputStream,
+  output: Output,
+  playlist: Option<Playlist>,
+  queued: Option<Track>,
   recording: Option<Vec<Key>>,
   renderer: Option<Renderer>,
   scaling: f32,
-  #[allow(unused)]
   sink: Sink,
+  source: u32,
+  source_names: Vec<String>,
+  sources: Vec<(Analyzer, Delay)>,
   start: Instant,
   state: State,
-  stream: Option<Box<dyn Stream>>,
+  stream: Option<Delay>,
+  title: Option<String>,
   translation: Vec2f,
+  transport: Option<Transport>,
   vertical: f32,
   window: Option<Arc<Window>>,
   wrap: bool,
@@ -25,6 +35,144 @@ pub(crate) struct App {
 }
 
 impl App {
+  const INPUT: &str = "input";
+  const MAIN: &str = "main";
+
+  fn action(&mut self, action: Action, gesture: Gesture) -> Result {
+    if action.continuous() {
+      let Gesture::Turn(parameter) = gesture else {
+        return Ok(());
+      };
+
+      self.state.parameter = parameter;
+
+      match action {
+        Action::Alpha => self.state.alpha = parameter,
+        Action::Db => self.state.db = parameter.value().into(),
+        Action::Horizontal => self.horizontal = parameter.bipolar(),
+        Action::Vertical => self.vertical = parameter.bipolar(),
+        Action::Zoom => self.zoom = parameter.bipolar(),
+        _ => {}
+      }
+
+      return Ok(());
+    }
+
+    if gesture != Gesture::Press {
+      return Ok(());
+    }
+
+    match action {
+      Action::Back => self.transport(|transport| transport.back()),
+      Action::Cue => self.transport(|transport| {
+        transport.cue();
+        Ok(())
+      }),
+      Action::Forward => self.transport(|transport| transport.forward()),
+      Action::Jump => self.transport(|transport| transport.jump()),
+      Action::Loop => self.transport(|transport| {
+        transport.loop_point();
+        Ok(())
+      }),
+      Action::Next => self.skip(1),
+      Action::Pause => self.pause(),
+      Action::Pop => {
+        self.state.filters.pop();
+      }
+      Action::Previous => self.skip(-1),
+      Action::Program(program) => {
+        self.state = program.state();
+        if let Some(db) = self.options.db {
+          self.state.db = db;
+        }
+      }
+      Action::Push(field, source) => self.state.filters.push(Filter {
+        color: invert_color(),
+        band: self.band,
+        source: match source {
+          Some(source) => Self::match_source(&source, &self.source_names)?,
+          None => self.source,
+        },
+        field,
+        wrap: self.wrap,
+        ..default()
+      }),
+      Action::ResetX => self.translation.x = 0.0,
+      Action::ResetY => self.translation.y = 0.0,
+      Action::ResetZoom => self.scaling = 1.0,
+      Action::Restart => self.transport(|transport| transport.restart()),
+      Action::Scale(scale) => self.state.filters.push(Filter {
+        position: Mat3f::new_scaling(scale),
+        wrap: self.wrap,
+        ..default()
+      }),
+      Action::Source(source) => self.source = Self::match_source(&source, &self.source_names)?,
+      Action::Translate(x, y) => self.state.filters.push(Filter {
+        position: Mat3f::new_translation(&Vec2f::new(x, y)),
+        wrap: self.wrap,
+        ..default()
+      }),
+      Action::Alpha
+      | Action::Db
+      | Action::Horizontal
+      | Action::Parameter
+      | Action::Vertical
+      | Action::Zoom => {}
+    }
+
+    Ok(())
+  }
+
+  fn adjust_latency(&mut self, ms: f32) {
+    let latency = Self::clamp_latency(
+      self.options.latency.unwrap_or_default() + ms,
+      self.lookahead,
+    );
+
+    self.options.latency = Some(latency);
+
+    if let Some(stream) = &mut self.stream {
+      stream.set_delay(Self::delay(latency, self.lookahead));
+    }
+
+    let count = self.sources.len();
+
+    for (i, (_, delay)) in self.sources.iter_mut().enumerate() {
+      // audio input is always the last extra source
+      let lookahead = if self.options.input && i + 1 == count {
+        Duration::ZERO
+      } else {
+        self.lookahead
+      };
+
+      delay.set_delay(Self::delay(latency, lookahead));
+    }
+  }
+
+  fn advance(&mut self, offset: isize) -> Option<(Entry, Track)> {
+    let lookahead = self.lookahead;
+
+    self
+      .playlist
+      .as_mut()?
+      .advance(offset, |path| Track::open(path, lookahead))
+  }
+
+  fn candidates(name: &str, names: &[String]) -> Vec<usize> {
+    if let Some(index) = names.iter().position(|candidate| candidate == name) {
+      return vec![index];
+    }
+
+    let lowercase = name.to_lowercase();
+
+    names
+      .iter()
+      .enumerate()
+      .filter(|(_, candidate)| candidate.to_lowercase().contains(&lowercase))
+      .map(|(index, _)| index)
+      .collect()
+  }
+
   fn capture(&mut self) -> Result {
     self.renderer.as_ref().unwrap().capture(|capture| {
       if let Err(err) = capture.save("capture.png".as_ref()) {
@@ -34,128 +182,411 @@ impl App {
     Ok(())
   }
 
-  pub(crate) fn error(self) -> Option<Error> {
-    self.error
+  fn clamp_latency(latency: f32, lookahead: Duration) -> f32 {
+    latency.max(-lookahead.as_secs_f32() * 1000.0)
   }
 
-  fn find_song(song: &str) -> Result<PathBuf> {
-    let song = RegexBuilder::new(song)
-      .case_insensitive(true)
-      .build()
-      .context(error::SongRegex)?;
+  fn delay(latency: f32, lookahead: Duration) -> Duration {
+    Duration::from_secs_f32((latency / 1000.0 + lookahead.as_secs_f32()).max(0.0))
+  }
 
-    let mut matches = Vec::<PathBuf>::new();
+  fn device(host: &cpal::Host, name: Option<&str>, input: bool) -> Result<rodio::Device> {
+    let Some(name) = name else {
+      return if input {
+        host
+          .default_input_device()
+          .context(error::AudioDefaultInputDevice)
+      } else {
+        host
+          .default_output_device()
+          .context(error::AudioDefaultOutputDevice)
+      };
+    };
 
-    let home = dirs::home_dir().context(error::Home)?;
+    let devices: Vec<rodio::Device> = if input {
+      host.input_devices().context(error::AudioDevices)?.collect()
+    } else {
+      host
+        .output_devices()
+        .context(error::AudioDevices)?
+        .collect()
+    };
 
-    let music = home.join("Music/Music/Media.localized/Music");
+    let names = devices
+      .iter()
+      .map(|device| device.name().context(error::AudioDeviceName))
+      .collect::<Result<Vec<String>>>()?;
 
-    for entry in WalkDir::new(&music) {
-      let entry = entry.context(error::SongWalk)?;
+    let index = Self::match_device(name, &names, input)?;
 
-      if entry.file_type().is_dir() {
-        continue;
-      }
+    Ok(devices.into_iter().nth(index).unwrap())
+  }
 
-      let path = entry.path();
+  pub(crate) fn error(self) -> Option<Error> {
+    self.error
+  }
 
-      let haystack = path.strip_prefix(&music).unwrap().with_extension("");
+  fn input(host: &cpal::Host, options: &Options) -> Result<Input> {
+    let input_device = Self::device(host, options.input_device.as_deref(), true)?;
 
-      let Some(haystack) = haystack.to_str() else {
-        continue;
-      };
+    let stream_config = Self::stream_config(
+      input_device
+        .supported_input_configs()
+        .context(error::AudioSupportedStreamConfigs)?,
+      options,
+      true,
+    )?;
 
-      if song.is_match(haystack) {
-        matches.push(path.into());
-      }
-    }
+    Input::new(input_device, stream_config)
+  }
+
+  fn match_device(name: &str, names: &[String], input: bool) -> Result<usize> {
+    let matches = Self::candidates(name, names);
 
-    if matches.len() > 1 {
-      return Err(error::SongAmbiguous { matches }.build());
+    match matches.as_slice() {
+      [index] => Ok(*index),
+      [] => Err(
+        error::AudioDeviceMatch {
+          candidates: names.to_vec(),
+          input,
+          name,
+        }
+        .build(),
+      ),
+      _ => Err(
+        error::AudioDeviceAmbiguous {
+          candidates: matches
+            .iter()
+            .map(|&index| names[index].clone())
+            .collect::<Vec<String>>(),
+          input,
+          name,
+        }
+        .build(),
+      ),
     }
+  }
 
-    match matches.into_iter().next() {
-      Some(path) => Ok(path),
-      None => Err(error::SongMatch { song }.build()),
+  fn match_source(name: &str, names: &[String]) -> Result<u32> {
+    let matches = Self::candidates(name, names);
+
+    match matches.as_slice() {
+      [index] => Ok(u32::try_from(*index).unwrap()),
+      [] => Err(
+        error::SourceMatch {
+          candidates: names.to_vec(),
+          name,
+        }
+        .build(),
+      ),
+      _ => Err(
+        error::SourceAmbiguous {
+          candidates: matches
+            .iter()
+            .map(|&index| names[index].clone())
+            .collect::<Vec<String>>(),
+          name,
+        }
+        .build(),
+      ),
     }
   }
 
   pub(crate) fn new(options: Options) -> Result<Self> {
+    let mapping = Mapping::load(&options)?;
+
     let host = cpal::default_host();
 
-    let output_device = host
-      .default_output_device()
-      .context(error::AudioDefaultOutputDevice)?;
+    let output = if options.silent {
+      Output::null()
+    } else {
+      let output_device = Self::device(&host, options.output_device.as_deref(), false)?;
 
-    let stream_config = Self::stream_config(
-      output_device
-        .supported_output_configs()
-        .context(error::AudioSupportedStreamConfigs)?,
-    )?;
+      let stream_config = Self::stream_config(
+        output_device
+          .supported_output_configs()
+          .context(error::AudioSupportedStreamConfigs)?,
+        &options,
+        false,
+      )?;
 
-    let output_stream = rodio::OutputStreamBuilder::from_device(output_device)
-      .context(error::AudioBuildOutputStream)?
-      .with_supported_config(&stream_config)
-      .open_stream()
-      .context(error::AudioBuildOutputStream)?;
+      Output::Device(
+        rodio::OutputStreamBuilder::from_device(output_device)
+          .context(error::AudioBuildOutputStream)?
+          .with_supported_config(&stream_config)
+          .open_stream()
+          .context(error::AudioBuildOutputStream)?,
+      )
+    };
 
-    let sink = Sink::connect_new(output_stream.mixer());
+    let sink = Sink::connect_new(output.mixer());
 
     if let Some(volume) = options.volume {
       sink.set_volume(volume);
     }
 
-    let stream: Option<Box<dyn Stream>> = if let Some(track) = &options.track {
-      let track = Track::new(track)?;
-      sink.append(track.clone());
-      Some(Box::new(track))
-    } else if let Some(song) = &options.song {
-      let track = Track::new(&Self::find_song(song)?)?;
+    let latency = options.latency.unwrap_or_default();
+
+    let lookahead = Duration::from_secs_f32((-latency / 1000.0).max(0.0));
+
+    let library = if options.song.is_some() || options.playlist.is_some() {
+      Some(Library::load(&options)?)
+    } else {
+      None
+    };
+
+    let playlist = options
+      .playlist
+      .as_deref()
+      .zip(library.as_ref())
+      .map(|(path, library)| Playlist::load(path, library))
+      .transpose()?;
+
+    let path = if let Some(track) = &options.track {
+      Some(track.clone())
+    } else if let Some(song) = &options.song
+      && let Some(library) = &library
+    {
+      Some(library.find(song)?)
+    } else {
+      playlist
+        .as_ref()
+        .map(|playlist| playlist.current().path.clone())
+    };
+
+    let mut stems = Vec::new();
+
+    let mut source_names = vec![Self::MAIN.to_owned()];
+
+    let track = if options.calibrate {
+      Some(Track::new(Click::new(), lookahead))
+    } else if let Some(stems_path) = &options.stems {
+      let mix = Mix::open(stems_path)?;
+      stems = mix.tracks().to_vec();
+      source_names.extend_from_slice(mix.names());
+      Some(Track::new(mix, lookahead))
+    } else if let Some(path) = &path {
+      Some(Track::open(path, lookahead)?)
+    } else {
+      None
+    };
+
+    let transport = track
+      .as_ref()
+      .map(|track| Transport::new(track.clone(), Self::seek_step(&options)));
+
+    let mut sources = stems
+      .into_iter()
+      .map(|stem| {
+        (
+          Analyzer::new(&options),
+          Delay::new(Box::new(stem), Self::delay(latency, lookahead)),
+        )
+      })
+      .collect::<Vec<(Analyzer, Delay)>>();
+
+    let primary: Option<Box<dyn Stream>> = if let Some(track) = track {
       sink.append(track.clone());
       Some(Box::new(track))
-    } else if options.input {
-      let input_device = host
-        .default_input_device()
-        .context(error::AudioDefaultInputDevice)?;
+    } else if let Some(signal) = options.signal {
+      Some(Box::new(Generator::new(signal)))
+    } else {
+      None
+    };
 
-      let stream_config = Self::stream_config(
-        input_device
-          .supported_input_configs()
-          .context(error::AudioSupportedStreamConfigs)?,
-      )?;
+    let stream: Option<Box<dyn Stream>> = if primary.is_some() {
+      if options.input {
+        source_names.push(Self::INPUT.into());
+        sources.push((
+          Analyzer::new(&options),
+          Delay::new(
+            Box::new(Self::input(&host, &options)?),
+            Self::delay(latency, Duration::ZERO),
+          ),
+        ));
+      }
 
-      Some(Box::new(Input::new(input_device, stream_config)?))
+      primary
+    } else if options.input {
+      if latency < 0.0 {
+        return Err(error::InputLatency { latency }.build());
+      }
+
+      Some(Box::new(Self::input(&host, &options)?))
     } else {
       None
     };
 
-    let mut state = options.program.map(Program::state).unwrap_or_default();
+    let stream = stream.map(|stream| Delay::new(stream, Self::delay(latency, lookahead)));
+
+    for name in mapping.sources() {
+      Self::match_source(name, &source_names)?;
+    }
+
+    let mut state = options
+      .program
+      .or(
+        playlist
+          .as_ref()
+          .and_then(|playlist| playlist.current().program),
+      )
+      .or(options.calibrate.then_some(Program::Calibrate))
+      .map(Program::state)
+      .unwrap_or_default();
 
     if let Some(db) = options.db {
       state.db = db;
     }
 
-    Ok(Self {
-      analyzer: Analyzer::new(),
+    let mut analyzer = Analyzer::new(&options);
+
+    if options.precompute
+      && let Some(path) = &path
+    {
+      analyzer.set_analysis(Analysis::load(path, &options)?);
+    }
+
+    let mut app = Self {
+      analyzer,
+      autopilot: Autopilot::default(),
+      band: None,
       error: None,
+      fading: None,
       horizontal: 0.0,
-      hub: Hub::new()?,
+      hub: Hub::new(&mapping)?,
+      lookahead,
       makro: Vec::new(),
+      mapping,
       options,
-      output_stream,
+      output,
+      playlist,
+      queued: None,
       recording: None,
       renderer: None,
       scaling: 1.0,
       sink,
+      source: 0,
+      source_names,
+      sources,
       start: Instant::now(),
       state,
       stream,
+      title: path.as_deref().and_then(Self::title),
       translation: Vec2f::zeros(),
+      transport,
       vertical: 0.0,
       window: None,
       wrap: true,
       zoom: 0.0,
-    })
+    };
+
+    app.queue();
+
+    Ok(app)
+  }
+
+  fn next(&mut self) {
+    if self.playlist.is_none() {
+      return;
+    }
+
+    let crossfade = self.options.crossfade.unwrap_or_default();
+    let volume = self.options.volume.unwrap_or(1.0);
+
+    if let Some((sink, start)) = &self.fading {
+      let t = start.elapsed().as_secs_f32() / crossfade;
+      if t >= 1.0 {
+        self.fading = None;
+        self.sink.set_volume(volume);
+      } else {
+        sink.set_volume(volume * (1.0 - t));
+        self.sink.set_volume(volume * t);
+      }
+      return;
+    }
+
+    let remaining = self
+      .transport
+      .as_ref()
+      .and_then(Transport::remaining)
+      .unwrap_or(Duration::MAX);
+
+    if crossfade > 0.0
+      && remaining.as_secs_f32() <= crossfade
+      && let Some((entry, track)) = self.advance(1)
+    {
+      let sink = Sink::connect_new(self.output.mixer());
+      sink.set_volume(0.0);
+      sink.append(track.clone());
+      if self.sink.is_paused() {
+        sink.pause();
+      }
+      self.fading = Some((mem::replace(&mut self.sink, sink), Instant::now()));
+      self.play(track, &entry);
+      return;
+    }
+
+    if !self.stream.as_ref().is_some_and(Stream::done) {
+      return;
+    }
+
+    let next = if let Some(track) = self.queued.take() {
+      self
+        .playlist
+        .as_mut()
+        .and_then(|playlist| playlist.skip(1).cloned())
+        .map(|entry| (entry, track))
+    } else {
+      self.advance(1).inspect(|(_, track)| {
+        self.sink.append(track.clone());
+      })
+    };
+
+    if let Some((entry, track)) = next {
+      self.play(track, &entry);
+      self.queue();
+    }
+  }
+
+  fn pause(&self) {
+    let paused = self.sink.is_paused();
+
+    for sink in iter::once(&self.sink).chain(self.fading.as_ref().map(|(sink, _)| sink)) {
+      if paused {
+        sink.play();
+      } else {
+        sink.pause();
+      }
+    }
+  }
+
+  fn play(&mut self, track: Track, entry: &Entry) {
+    log::info!("playing {}", entry.path.display());
+
+    self.stream = Some(Delay::new(
+      Box::new(track.clone()),
+      Self::delay(self.options.latency.unwrap_or_default(), self.lookahead),
+    ));
+
+    self.transport = Some(Transport::new(track, Self::seek_step(&self.options)));
+
+    self.title = Self::title(&entry.path);
+
+    self.analyzer.reset();
+
+    if let Some(program) = entry.program {
+      self.state = program.state();
+      if let Some(db) = self.options.db {
+        self.state.db = db;
+      }
+    }
+
+    if self.options.precompute {
+      match Analysis::load(&entry.path, &self.options) {
+        Ok(analysis) => self.analyzer.set_analysis(analysis),
+        Err(err) => log::warn!("failed to analyze {}: {err}", entry.path.display()),
+      }
+    }
   }
 
   fn press(&mut self, event_loop: &ActiveEventLoop, key: Key) {
@@ -175,6 +606,10 @@ impl App {
             event_loop.exit();
           }
         }
+        "," => self.transport(|transport| transport.back()),
+        "." => self.transport(|transport| transport.forward()),
+        "[" => self.adjust_latency(-5.0),
+        "]" => self.adjust_latency(5.0),
         "@" => {
           for key in self.makro.clone() {
             self.press(event_loop, key);
@@ -183,12 +618,19 @@ impl App {
         }
         "a" => self.state.filters.push(Filter {
           color: invert_color(),
+          band: self.band,
+          source: self.source,
           field: Field::All,
           wrap: self.wrap,
           ..default()
         }),
+        "b" => {
+          self.band = Band::iter().nth(self.band.map_or(0, |band| band as usize + 1));
+        }
         "c" => self.state.filters.push(Filter {
           color: invert_color(),
+          band: self.band,
+          source: self.source,
           field: Field::Circle,
           wrap: self.wrap,
           ..default()
@@ -198,20 +640,74 @@ impl App {
           wrap: self.wrap,
           ..default()
         }),
+        "e" => {
+          self.source = (self.source + 1) % u32::try_from(self.sources.len() + 1).unwrap();
+        }
         "f" => {
           self.options.fit = !self.options.fit;
         }
+        "g" => self.state.filters.push(Filter {
+          color: invert_color(),
+          band: self.band,
+          source: self.source,
+          field: Field::Goniometer,
+          wrap: self.wrap,
+          ..default()
+        }),
+        "h" => self.state.filters.push(Filter {
+          band: self.band,
+          source: self.source,
+          field: Field::All,
+          hue: true,
+          wrap: self.wrap,
+          ..default()
+        }),
+        "i" => self.transport(|transport| {
+          transport.loop_point();
+          Ok(())
+        }),
+        "j" => self.transport(|transport| transport.jump()),
+        "k" => self.state.filters.push(Filter {
+          color: invert_color(),
+          band: self.band,
+          source: self.source,
+          field: Field::Chroma,
+          wrap: self.wrap,
+          ..default()
+        }),
         "l" => self.state.filters.push(Filter {
           color: invert_color(),
+          band: self.band,
+          source: self.source,
           field: Field::Frequencies,
           wrap: self.wrap,
           ..default()
         }),
+        "m" => self.transport(|transport| {
+          transport.cue();
+          Ok(())
+        }),
         "n" => self.state.filters.push(Filter {
           field: Field::None,
           wrap: self.wrap,
           ..default()
         }),
+        "o" => self.state.filters.push(Filter {
+          color: invert_color(),
+          band: self.band,
+          source: self.source,
+          field: Field::Correlation,
+          wrap: self.wrap,
+          ..default()
+        }),
+        "p" => self.state.filters.push(Filter {
+          band: self.band,
+          source: self.source,
+          color: invert_color(),
+          field: Field::Peaks,
+          wrap: self.wrap,
+          ..default()
+        }),
         "q" => {
           if let Some(recording) = self.recording.take() {
             self.makro = recording;
@@ -225,6 +721,8 @@ impl App {
         }
         "s" => self.state.filters.push(Filter {
           color: invert_color(),
+          band: self.band,
+          source: self.source,
           field: Field::Samples,
           wrap: self.wrap,
           ..default()
@@ -232,15 +730,36 @@ impl App {
         "t" => {
           self.options.tile = !self.options.tile;
         }
+        "u" => {
+          self.options.auto_gain = !self.options.auto_gain;
+        }
+        "v" => self.state.filters.push(Filter {
+          color: invert_color(),
+          band: self.band,
+          source: self.source,
+          field: Field::Waveform,
+          wrap: self.wrap,
+          ..default()
+        }),
         "w" => {
           self.wrap = !self.wrap;
         }
         "x" => self.state.filters.push(Filter {
           color: invert_color(),
+          band: self.band,
+          source: self.source,
           field: Field::X,
           wrap: self.wrap,
           ..default()
         }),
+        "y" => self.state.filters.push(Filter {
+          color: invert_color(),
+          band: self.band,
+          source: self.source,
+          field: Field::Spectrogram,
+          wrap: self.wrap,
+          ..default()
+        }),
         "z" => self.state.filters.push(Filter {
           position: Mat3f::new_scaling(2.0),
           wrap: self.wrap,
@@ -264,6 +783,10 @@ impl App {
             ..default()
           });
         }
+        NamedKey::Home => self.transport(|transport| transport.restart()),
+        NamedKey::PageDown => self.skip(1),
+        NamedKey::PageUp => self.skip(-1),
+        NamedKey::Space => self.pause(),
         _ => {}
       },
       _ => {}
@@ -274,79 +797,54 @@ impl App {
     }
   }
 
+  fn queue(&mut self) {
+    if self.options.crossfade.unwrap_or_default() > 0.0 {
+      return;
+    }
+
+    // entries that fail to open are skipped by `advance` when playback reaches them
+    if let Some(entry) = self.playlist.as_ref().and_then(|playlist| playlist.peek(1))
+      && let Ok(track) = Track::open(&entry.path, self.lookahead)
+    {
+      self.sink.append(track.clone());
+      self.queued = Some(track);
+    }
+  }
+
   fn redraw(&mut self, event_loop: &ActiveEventLoop) {
-    for message in self.hub.messages().lock().unwrap().drain(..) {
-      match message.tuple() {
-        (Device::Spectra, 0, Event::Button(true)) => self.state.filters.push(Filter {
-          color: invert_color(),
-          field: Field::Top,
-          wrap: self.wrap,
-          ..default()
-        }),
-        (Device::Spectra, 1, Event::Button(true)) => self.state.filters.push(Filter {
-          color: invert_color(),
-          field: Field::Bottom,
-          wrap: self.wrap,
-          ..default()
-        }),
-        (Device::Spectra, 2, Event::Button(true)) => self.state.filters.push(Filter {
-          color: invert_color(),
-          field: Field::X,
-          wrap: self.wrap,
-          ..default()
-        }),
-        (Device::Spectra, 3, Event::Button(true)) => self.state.filters.push(Filter {
-          color: invert_color(),
-          field: Field::Circle,
-          wrap: self.wrap,
-          ..default()
-        }),
-        (Device::Spectra, 4, Event::Button(true)) => self.state.filters.push(Filter {
-          position: Mat3f::new_scaling(2.0),
-          wrap: self.wrap,
-          ..default()
-        }),
-        (Device::Spectra, 5, Event::Button(true)) => self.state.filters.push(Filter {
-          position: Mat3f::new_scaling(0.5),
-          wrap: self.wrap,
-          ..default()
-        }),
-        (Device::Spectra, 6, Event::Button(true)) => self.state.filters.push(Filter {
-          position: Mat3f::new_translation(&Vec2f::new(-0.1, 0.0)),
-          wrap: self.wrap,
-          ..default()
-        }),
-        (Device::Spectra, 7, Event::Button(true)) => self.state.filters.push(Filter {
-          position: Mat3f::new_translation(&Vec2f::new(0.1, 0.0)),
-          wrap: self.wrap,
-          ..default()
-        }),
-        (Device::Spectra, 8, Event::Button(true)) => {
-          self.state.filters.pop();
-        }
-        (Device::Twister, control, Event::Button(true)) => match control {
-          4 => self.translation.x = 0.0,
-          5 => self.translation.y = 0.0,
-          6 => self.scaling = 1.0,
-          _ => {}
-        },
-        (Device::Twister, control, Event::Encoder(parameter)) => {
-          self.state.parameter = parameter;
-          match control {
-            0 => self.state.alpha = parameter,
-            1 => self.state.db = parameter.value() as f32,
-            4 => self.horizontal = parameter.bipolar(),
-            5 => self.vertical = parameter.bipolar(),
-            6 => self.zoom = parameter.bipolar(),
-            _ => {}
-          }
+    let messages = self
+      .hub
+      .messages()
+      .lock()
+      .unwrap()
+      .drain(..)
+      .collect::<Vec<Message>>();
+
+    for message in messages {
+      for (action, gesture) in self.mapping.actions(&message) {
+        if let Err(err) = self.action(action, gesture) {
+          self.error = Some(err);
+          event_loop.exit();
+          return;
         }
-        _ => {}
       }
     }
 
+    self.next();
+
     if let Some(stream) = self.stream.as_mut() {
-      self.analyzer.update(stream.as_mut(), &self.state);
+      self.analyzer.update(stream, &self.state);
+    }
+
+    for (analyzer, stream) in &mut self.sources {
+      analyzer.update(stream, &self.state);
+    }
+
+    for section in self.analyzer.take_sections() {
+      log::info!("section: {}", section.name());
+      if self.options.autopilot {
+        self.autopilot.section(section, &mut self.state);
+      }
     }
 
     let now = Instant::now();
@@ -363,13 +861,30 @@ impl App {
       ..default()
     });
 
-    if let Err(err) =
-      self
-        .renderer
-        .as_mut()
-        .unwrap()
-        .render(&self.options, &self.analyzer, &self.state)
-    {
+    let playhead = self
+      .transport
+      .as_ref()
+      .map(|transport| transport.playhead(self.sink.is_paused()));
+
+    let analyzers = iter::once(&self.analyzer)
+      .chain(self.sources.iter().map(|(analyzer, _)| analyzer))
+      .collect::<Vec<&Analyzer>>();
+
+    // audio input is the main stream or the last extra source
+    let input = if self.options.input {
+      analyzers.last().copied()
+    } else {
+      None
+    };
+
+    if let Err(err) = self.renderer.as_mut().unwrap().render(
+      &self.options,
+      &analyzers,
+      input,
+      &self.state,
+      playhead,
+      self.title.as_deref(),
+    ) {
       self.error = Some(err);
       event_loop.exit();
       return;
@@ -380,27 +895,121 @@ impl App {
     self.window().request_redraw();
   }
 
+  fn seek_step(options: &Options) -> Duration {
+    Duration::from_secs_f32(options.seek_step.unwrap_or(5.0).max(0.0))
+  }
+
+  fn skip(&mut self, offset: isize) {
+    let Some((entry, track)) = self.advance(offset) else {
+      return;
+    };
+
+    let paused = self.sink.is_paused();
+
+    self.fading = None;
+    self.queued = None;
+    self.sink.clear();
+    self.sink.set_volume(self.options.volume.unwrap_or(1.0));
+    self.sink.append(track.clone());
+
+    if !paused {
+      self.sink.play();
+    }
+
+    self.play(track, &entry);
+
+    self.queue();
+  }
+
   fn stream_config(
     configs: impl Iterator<Item = SupportedStreamConfigRange>,
+    options: &Options,
+    input: bool,
   ) -> Result<SupportedStreamConfig> {
+    let configs = configs.collect::<Vec<SupportedStreamConfigRange>>();
+
+    if configs.is_empty() {
+      return Err(error::AudioSupportedStreamConfig.build());
+    }
+
+    let channels = if input { options.input_channels } else { None };
+
     let config = configs
-      .max_by_key(SupportedStreamConfigRange::max_sample_rate)
-      .context(error::AudioSupportedStreamConfig)?;
+      .iter()
+      .filter(|config| channels.is_none_or(|channels| config.channels() == channels))
+      .filter(|config| {
+        options.sample_rate.is_none_or(|sample_rate| {
+          (config.min_sample_rate().0..=config.max_sample_rate().0).contains(&sample_rate)
+        })
+      })
+      .filter(|config| {
+        options
+          .buffer_size
+          .is_none_or(|buffer_size| match config.buffer_size() {
+            SupportedBufferSize::Range { min, max } => (*min..=*max).contains(&buffer_size),
+            SupportedBufferSize::Unknown => true,
+          })
+      })
+      .max_by_key(|config| config.max_sample_rate())
+      .with_context(|| error::AudioStreamConfigMatch {
+        candidates: configs
+          .iter()
+          .map(|config| {
+            format!(
+              "{} channels, {}, {}–{} Hz, buffer {}",
+              config.channels(),
+              config.sample_format(),
+              config.min_sample_rate().0,
+              config.max_sample_rate().0,
+              match config.buffer_size() {
+                SupportedBufferSize::Range { min, max } => format!("{min}–{max}"),
+                SupportedBufferSize::Unknown => "unknown".into(),
+              },
+            )
+          })
+          .collect::<Vec<String>>(),
+        input,
+      })?;
 
     Ok(SupportedStreamConfig::new(
       config.channels(),
-      config.max_sample_rate(),
+      options
+        .sample_rate
+        .map_or(config.max_sample_rate(), cpal::SampleRate),
       match config.buffer_size() {
-        SupportedBufferSize::Range { min, .. } => SupportedBufferSize::Range {
-          min: *min,
-          max: *min,
-        },
+        SupportedBufferSize::Range { min, .. } => {
+          let size = options.buffer_size.unwrap_or(*min);
+          SupportedBufferSize::Range {
+            min: size,
+            max: size,
+          }
+        }
         SupportedBufferSize::Unknown => SupportedBufferSize::Unknown,
       },
       config.sample_format(),
     ))
   }
 
+  fn title(path: &Path) -> Option<String> {
+    let song = Song::probe(path, String::new(), 0)?;
+
+    match (song.artist.is_empty(), song.title.is_empty()) {
+      (false, false) => Some(format!("{} — {}", song.artist, song.title)),
+      (true, false) => Some(song.title),
+      _ => path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned()),
+    }
+  }
+
+  fn transport(&mut self, action: impl FnOnce(&mut Transport) -> Result) {
+    if let Some(transport) = &mut self.transport
+      && let Err(err) = action(transport)
+    {
+      eprintln!("transport error: {err}");
+    }
+  }
+
   fn window(&self) -> &Window {
     self.window.as_ref().unwrap()
   }
@@ -442,7 +1051,11 @@ impl ApplicationHandler for App {
 
       self.window = Some(window.clone());
 
-      let renderer = match pollster::block_on(Renderer::new(&self.options, window)) {
+      let renderer = match pollster::block_on(Renderer::new(
+        &self.options,
+        window,
+        u32::try_from(self.sources.len() + 1).unwrap(),
+      )) {
         Ok(renderer) => renderer,
         Err(err) => {
           self.error = Some(err);
@@ -479,3 +1092,67 @@ impl ApplicationHandler for App {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn clamp_latency() {
+    let lookahead = Duration::from_millis(20);
+
+    assert_eq!(App::clamp_latency(10.0, lookahead), 10.0);
+    assert_eq!(App::clamp_latency(-15.0, lookahead), -15.0);
+    assert_eq!(App::clamp_latency(-25.0, lookahead), -20.0);
+    assert_eq!(App::clamp_latency(-5.0, Duration::ZERO), 0.0);
+
+    assert_eq!(
+      App::delay(App::clamp_latency(-25.0, lookahead), lookahead),
+      Duration::ZERO,
+    );
+  }
+
+  #[test]
+  fn match_source() {
+    let names = ["main", "bass", "drums", "other", "vocals"]
+      .map(String::from)
+      .to_vec();
+
+    assert_eq!(App::match_source("drums", &names).unwrap(), 2);
+    assert_eq!(App::match_source("Vocal", &names).unwrap(), 4);
+    assert_eq!(App::match_source("main", &names).unwrap(), 0);
+
+    assert!(matches!(
+      App::match_source("s", &names),
+      Err(Error::SourceAmbiguous { candidates, .. }) if candidates.len() == 3,
+    ));
+
+    assert!(matches!(
+      App::match_source("piano", &names),
+      Err(Error::SourceMatch { candidates, .. }) if candidates.len() == 5,
+    ));
+  }
+
+  #[test]
+  fn match_device() {
+    let names = ["MacBook Pro Speakers", "BlackHole 2ch", "BlackHole 16ch"]
+      .map(String::from)
+      .to_vec();
+
+    assert_eq!(
+      App::match_device("BlackHole 2ch", &names, false).unwrap(),
+      1
+    );
+    assert_eq!(App::match_device("speakers", &names, false).unwrap(), 0);
+
+    assert!(matches!(
+      App::match_device("blackhole", &names, false),
+      Err(Error::AudioDeviceAmbiguous { candidates, .. }) if candidates.len() == 2,
+    ));
+
+    assert!(matches!(
+      App::match_device("scarlett", &names, true),
+      Err(Error::AudioDeviceMatch { candidates, .. }) if candidates.len() == 3,
+    ));
+  }
+}
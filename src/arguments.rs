@@ -24,6 +24,7 @@ pub(crate) struct Arguments {
 
 impl Arguments {
   pub(crate) fn run(self) -> Result {
+    self.options.validate()?;
     self.subcommand.unwrap_or_default().run(self.options)
   }
 }
@@ -0,0 +1,27 @@
+use super::*;
+
+#[derive(Default)]
+pub(crate) struct Autopilot {
+  changes: usize,
+}
+
+impl Autopilot {
+  pub(crate) fn section(&mut self, section: Section, state: &mut State) {
+    let programs: &[Program] = match section {
+      Section::Chorus => &[Program::Bands, Program::Peaks, Program::Chroma],
+      Section::Drop => &[Program::Highwaystar, Program::Beat, Program::Spectrogram],
+      Section::Intro => &[Program::Circle, Program::Frequencies],
+      Section::Outro => {
+        state.filters.pop();
+        return;
+      }
+      Section::Verse => &[Program::Rip, Program::Middle, Program::X],
+    };
+
+    let program = programs[self.changes % programs.len()];
+
+    self.changes += 1;
+
+    state.filters = program.state().filters;
+  }
+}
@@ -0,0 +1,39 @@
+use super::*;
+
+#[derive(Clone, Copy, EnumCount, EnumIter, IntoStaticStr)]
+#[strum(serialize_all = "kebab-case")]
+#[repr(u32)]
+pub(crate) enum Band {
+  Bass,
+  High,
+  LowMid,
+  Mid,
+  Sub,
+}
+
+impl Band {
+  pub(crate) fn attack(self) -> f32 {
+    match self {
+      Self::Bass | Self::Sub => 0.010,
+      Self::High | Self::LowMid | Self::Mid => 0.005,
+    }
+  }
+
+  pub(crate) fn range(self) -> (f32, f32) {
+    match self {
+      Self::Bass => (60.0, 250.0),
+      Self::High => (4_000.0, 15_000.0),
+      Self::LowMid => (250.0, 500.0),
+      Self::Mid => (500.0, 4_000.0),
+      Self::Sub => (20.0, 60.0),
+    }
+  }
+
+  pub(crate) fn release(self) -> f32 {
+    match self {
+      Self::Bass | Self::Sub => 0.250,
+      Self::LowMid | Self::Mid => 0.150,
+      Self::High => 0.080,
+    }
+  }
+}
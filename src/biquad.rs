@@ -0,0 +1,46 @@
+use super::*;
+
+#[derive(Clone, Copy, Default)]
+pub(crate) struct Biquad {
+  a: [f32; 2],
+  b: [f32; 3],
+  x: [f32; 2],
+  y: [f32; 2],
+}
+
+impl Biquad {
+  pub(crate) fn high_pass(sample_rate: f32, frequency: f32, q: f32) -> Self {
+    let k = (PI * frequency / sample_rate).tan();
+    let a0 = 1.0 + k / q + k * k;
+    Self {
+      a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+      b: [1.0, -2.0, 1.0],
+      ..default()
+    }
+  }
+
+  pub(crate) fn high_shelf(sample_rate: f32, frequency: f32, q: f32, db: f32) -> Self {
+    let k = (PI * frequency / sample_rate).tan();
+    let vh = 10f32.powf(db / 20.0);
+    let vb = vh.powf(0.499_666_77);
+    let a0 = 1.0 + k / q + k * k;
+    Self {
+      a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+      b: [
+        (vh + vb * k / q + k * k) / a0,
+        2.0 * (k * k - vh) / a0,
+        (vh - vb * k / q + k * k) / a0,
+      ],
+      ..default()
+    }
+  }
+
+  pub(crate) fn process(&mut self, x: f32) -> f32 {
+    let y = self.b[0] * x + self.b[1] * self.x[0] + self.b[2] * self.x[1]
+      - self.a[0] * self.y[0]
+      - self.a[1] * self.y[1];
+    self.x = [x, self.x[0]];
+    self.y = [y, self.y[0]];
+    y
+  }
+}
@@ -0,0 +1,59 @@
+use super::*;
+
+#[derive(Default)]
+pub(crate) struct Channel {
+  buffer: Vec<Complex<f32>>,
+  samples: Vec<f32>,
+  spectrum: Vec<f32>,
+}
+
+impl Channel {
+  pub(crate) fn analyze(
+    &mut self,
+    fft: &dyn Fft<f32>,
+    window: &[f32],
+    scratch: &mut [Complex<f32>],
+  ) {
+    let size = window.len();
+
+    self.buffer.clear();
+    self.buffer.extend(
+      iter::repeat_n(0.0, size - self.samples.len())
+        .chain(self.samples.iter().copied())
+        .zip(window)
+        .map(|(sample, weight)| Complex::from(sample * weight)),
+    );
+
+    fft.process_with_scratch(&mut self.buffer, scratch);
+
+    let scale = 1.0 / window.iter().sum::<f32>();
+
+    self.spectrum.clear();
+    self
+      .spectrum
+      .extend(self.buffer[..=size / 2].iter().enumerate().map(|(i, c)| {
+        let weight = if i == 0 || i * 2 == size { 1.0 } else { 2.0 };
+        c.norm() * weight * scale
+      }));
+  }
+
+  pub(crate) fn clear(&mut self) {
+    self.samples.clear();
+    self.spectrum.clear();
+  }
+
+  pub(crate) fn extend(&mut self, samples: impl IntoIterator<Item = f32>, size: usize) {
+    self.samples.extend(samples);
+    self
+      .samples
+      .drain(..self.samples.len().saturating_sub(size));
+  }
+
+  pub(crate) fn samples(&self) -> &[f32] {
+    &self.samples
+  }
+
+  pub(crate) fn spectrum(&self) -> &[f32] {
+    &self.spectrum
+  }
+}
@@ -0,0 +1,76 @@
+use super::*;
+
+pub(crate) struct Chroma {
+  envelopes: [Envelope; Self::PITCH_CLASSES],
+}
+
+impl Chroma {
+  const MAX_FREQUENCY: f32 = 5000.0;
+  const MIN_FREQUENCY: f32 = 50.0;
+  pub(crate) const PITCH_CLASSES: usize = 12;
+
+  pub(crate) fn new(envelope: Envelope) -> Self {
+    Self {
+      envelopes: [envelope; Self::PITCH_CLASSES],
+    }
+  }
+
+  pub(crate) fn pitch(&self) -> Option<u32> {
+    let (pitch, value) = self
+      .envelopes
+      .iter()
+      .map(|envelope| envelope.value())
+      .enumerate()
+      .max_by(|a, b| a.1.total_cmp(&b.1))?;
+
+    (value > 0.0).then(|| pitch.try_into().unwrap())
+  }
+
+  pub(crate) fn pitch_class(frequency: f32) -> usize {
+    (12.0 * (frequency / 440.0).log2() + 69.0)
+      .round()
+      .rem_euclid(12.0)
+      .into_usize()
+  }
+
+  pub(crate) fn reset(&mut self) {
+    for envelope in &mut self.envelopes {
+      envelope.reset();
+    }
+  }
+
+  pub(crate) fn update(&mut self, spectrum: &[f32], spacing: f32, elapsed: f32) {
+    let mut energies = [0.0; Self::PITCH_CLASSES];
+
+    let start = (Self::MIN_FREQUENCY / spacing).ceil().into_usize();
+    let end = ((Self::MAX_FREQUENCY / spacing).into_usize() + 1).min(spectrum.len());
+
+    for (i, magnitude) in spectrum.iter().enumerate().take(end).skip(start) {
+      energies[Self::pitch_class(i as f32 * spacing)] += magnitude * magnitude;
+    }
+
+    let max = energies.iter().copied().fold(0.0, f32::max);
+
+    for (envelope, energy) in self.envelopes.iter_mut().zip(energies) {
+      envelope.update(if max > 0.0 { energy / max } else { 0.0 }, elapsed);
+    }
+  }
+
+  pub(crate) fn values(&self) -> [f32; Self::PITCH_CLASSES] {
+    self.envelopes.map(Envelope::value)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn pitch_class() {
+    assert_eq!(Chroma::pitch_class(440.0), 9);
+    assert_eq!(Chroma::pitch_class(261.63), 0);
+    assert_eq!(Chroma::pitch_class(130.81), 0);
+    assert_eq!(Chroma::pitch_class(277.18), 1);
+    assert_eq!(Chroma::pitch_class(493.88), 11);
+  }
+}
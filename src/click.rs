@@ -0,0 +1,56 @@
+use super::*;
+
+pub(crate) struct Click {
+  sample: u32,
+}
+
+impl Click {
+  const BPM: u32 = 120;
+  const DURATION: f32 = 0.01;
+  const FREQUENCY: f32 = 2000.0;
+  const SAMPLE_RATE: u32 = 48_000;
+
+  pub(crate) fn burst(t: f32) -> f32 {
+    if t < Self::DURATION {
+      (TAU * Self::FREQUENCY * t).sin() * (1.0 - t / Self::DURATION)
+    } else {
+      0.0
+    }
+  }
+
+  pub(crate) fn new() -> Self {
+    Self { sample: 0 }
+  }
+}
+
+impl Iterator for Click {
+  type Item = f32;
+
+  fn next(&mut self) -> Option<f32> {
+    let period = Self::SAMPLE_RATE * 60 / Self::BPM;
+
+    let t = (self.sample % period) as f32 / Self::SAMPLE_RATE as f32;
+
+    self.sample = (self.sample + 1) % period;
+
+    Some(Self::burst(t))
+  }
+}
+
+impl Source for Click {
+  fn channels(&self) -> u16 {
+    1
+  }
+
+  fn current_span_len(&self) -> Option<usize> {
+    None
+  }
+
+  fn sample_rate(&self) -> u32 {
+    Self::SAMPLE_RATE
+  }
+
+  fn total_duration(&self) -> Option<Duration> {
+    None
+  }
+}
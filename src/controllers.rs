@@ -0,0 +1,70 @@
+use {
+  super::*,
+  midly::num::{u7, u14},
+};
+
+#[derive(Default)]
+pub(crate) struct Controllers {
+  msb: [[Option<u7>; 32]; 16],
+  wide: [[bool; 32]; 16],
+}
+
+impl Controllers {
+  pub(crate) const LSB: u8 = 32;
+
+  pub(crate) fn combine(msb: u7, lsb: u7) -> u14 {
+    u14::from(u16::from(u8::from(msb)) << 7 | u16::from(u8::from(lsb)))
+  }
+
+  pub(crate) fn control_change(&mut self, channel: u8, controller: u8, value: u7) -> (u8, Event) {
+    // controllers 0 through 31 send the most significant bits of a 14-bit
+    // value, and controllers 32 through 63 the least significant bits, but
+    // only controllers declared as 14-bit are paired, since many devices use
+    // 32 through 63 as independent 7-bit controllers
+    //
+    // receiving the most significant bits resets the least significant bits
+    // to zero, and devices may omit the least significant bits when they are
+    // unchanged, so both halves produce a 14-bit value
+    let channel = usize::from(channel);
+
+    if controller < Self::LSB && self.wide[channel][usize::from(controller)] {
+      self.msb[channel][usize::from(controller)] = Some(value);
+      return (
+        controller,
+        Event::ControlChange14 {
+          value: Self::combine(value, 0.into()),
+        },
+      );
+    }
+
+    if (Self::LSB..Self::LSB * 2).contains(&controller)
+      && self.wide[channel][usize::from(controller - Self::LSB)]
+      && let Some(msb) = self.msb[channel][usize::from(controller - Self::LSB)]
+    {
+      return (
+        controller - Self::LSB,
+        Event::ControlChange14 {
+          value: Self::combine(msb, value),
+        },
+      );
+    }
+
+    (controller, Event::ControlChange { value })
+  }
+
+  pub(crate) fn new(wide: impl IntoIterator<Item = (u8, u8)>) -> Self {
+    let mut controllers = Self::default();
+
+    for (channel, controller) in wide {
+      if let Some(wide) = controllers
+        .wide
+        .get_mut(usize::from(channel))
+        .and_then(|channel| channel.get_mut(usize::from(controller)))
+      {
+        *wide = true;
+      }
+    }
+
+    controllers
+  }
+}
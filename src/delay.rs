@@ -0,0 +1,64 @@
+use super::*;
+
+pub(crate) struct Delay {
+  delay: Duration,
+  pending: VecDeque<(Instant, Vec<f32>)>,
+  stream: Box<dyn Stream>,
+}
+
+impl Delay {
+  pub(crate) fn new(stream: Box<dyn Stream>, delay: Duration) -> Self {
+    Self {
+      delay,
+      pending: VecDeque::new(),
+      stream,
+    }
+  }
+
+  pub(crate) fn set_delay(&mut self, delay: Duration) {
+    self.delay = delay;
+  }
+}
+
+impl Stream for Delay {
+  fn channels(&self) -> u16 {
+    self.stream.channels()
+  }
+
+  fn done(&self) -> bool {
+    self.stream.done() && self.pending.is_empty()
+  }
+
+  fn drain(&mut self, samples: &mut Vec<f32>) {
+    let now = Instant::now();
+
+    let mut received = Vec::new();
+    self.stream.drain(&mut received);
+
+    if !received.is_empty() {
+      self.pending.push_back((now, received));
+    }
+
+    while let Some((instant, _)) = self.pending.front() {
+      if now.duration_since(*instant) < self.delay {
+        break;
+      }
+
+      samples.append(&mut self.pending.pop_front().unwrap().1);
+    }
+  }
+
+  fn sample_rate(&self) -> u32 {
+    self.stream.sample_rate()
+  }
+
+  fn take_seek(&mut self) -> Option<usize> {
+    let seek = self.stream.take_seek();
+
+    if seek.is_some() {
+      self.pending.clear();
+    }
+
+    seek
+  }
+}
@@ -1,5 +0,0 @@
-#[derive(Clone, Copy, Debug)]
-pub(crate) enum Device {
-  Spectra,
-  Twister,
-}
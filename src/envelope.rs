@@ -0,0 +1,40 @@
+#[derive(Clone, Copy)]
+pub(crate) struct Envelope {
+  attack: f32,
+  release: f32,
+  value: f32,
+}
+
+impl Envelope {
+  pub(crate) fn new(attack: f32, release: f32) -> Self {
+    Self {
+      attack,
+      release,
+      value: 0.0,
+    }
+  }
+
+  pub(crate) fn reset(&mut self) {
+    self.value = 0.0;
+  }
+
+  pub(crate) fn update(&mut self, target: f32, elapsed: f32) {
+    let time = if target > self.value {
+      self.attack
+    } else {
+      self.release
+    };
+
+    let alpha = if time > 0.0 {
+      1.0 - (-elapsed / time).exp()
+    } else {
+      1.0
+    };
+
+    self.value += (target - self.value) * alpha;
+  }
+
+  pub(crate) fn value(self) -> f32 {
+    self.value
+  }
+}
@@ -15,8 +15,30 @@ pub(crate) enum Error {
   },
   #[snafu(display("failed to get default audio input device"))]
   AudioDefaultInputDevice { backtrace: Option<Backtrace> },
-  #[snafu(display("failed to get default audio output device"))]
+  #[snafu(display("failed to get default audio output device, use `--silent` to run without one"))]
   AudioDefaultOutputDevice { backtrace: Option<Backtrace> },
+  #[snafu(display(
+    "more than one audio {} device matches `{name}`: {}",
+    if *input { "input" } else { "output" },
+    candidates.join(", "),
+  ))]
+  AudioDeviceAmbiguous {
+    backtrace: Option<Backtrace>,
+    candidates: Vec<String>,
+    input: bool,
+    name: String,
+  },
+  #[snafu(display(
+    "no audio {} device matches `{name}`, candidates: {}",
+    if *input { "input" } else { "output" },
+    candidates.join(", "),
+  ))]
+  AudioDeviceMatch {
+    backtrace: Option<Backtrace>,
+    candidates: Vec<String>,
+    input: bool,
+    name: String,
+  },
   #[snafu(display("failed to get audio device name"))]
   AudioDeviceName {
     backtrace: Option<Backtrace>,
@@ -32,6 +54,16 @@ pub(crate) enum Error {
     backtrace: Option<Backtrace>,
     source: cpal::PlayStreamError,
   },
+  #[snafu(display(
+    "no supported audio {} stream config matches, candidates: {}",
+    if *input { "input" } else { "output" },
+    candidates.join("; "),
+  ))]
+  AudioStreamConfigMatch {
+    backtrace: Option<Backtrace>,
+    candidates: Vec<String>,
+    input: bool,
+  },
   #[snafu(display("failed to get supported stream config"))]
   AudioSupportedStreamConfig { backtrace: Option<Backtrace> },
   #[snafu(display("failed to get supported stream configs"))]
@@ -39,6 +71,8 @@ pub(crate) enum Error {
     backtrace: Option<Backtrace>,
     source: cpal::SupportedStreamConfigsError,
   },
+  #[snafu(display("could not get cache directory"))]
+  CacheDir { backtrace: Option<Backtrace> },
   #[snafu(display("failed to create overlay renderer"))]
   CreateOverlayRenderer {
     backtrace: Option<Backtrace>,
@@ -88,13 +122,52 @@ pub(crate) enum Error {
     backtrace: Option<Backtrace>,
     character: char,
   },
+  #[snafu(display(
+    "invalid frequency range {min} Hz to {max} Hz, minimum must be at least 0 Hz and below \
+     maximum"
+  ))]
+  FrequencyRange {
+    backtrace: Option<Backtrace>,
+    max: f32,
+    min: f32,
+  },
   #[snafu(display("could not get home directory"))]
   Home { backtrace: Option<Backtrace> },
+  #[snafu(display("cannot advance analysis of audio input by {} ms", -latency))]
+  InputLatency {
+    backtrace: Option<Backtrace>,
+    latency: f32,
+  },
   #[snafu(display("internal error: {message}"))]
   Internal {
     backtrace: Option<Backtrace>,
     message: String,
   },
+  #[snafu(display("invalid action `{action}`"))]
+  LearnAction {
+    action: String,
+    backtrace: Option<Backtrace>,
+    source: ActionError,
+  },
+  #[snafu(display("`learn` requires a mapping file, pass one with `--mapping`"))]
+  LearnMapping { backtrace: Option<Backtrace> },
+  #[snafu(display("cannot learn continuous action `{action}` from a program change"))]
+  LearnProgram {
+    action: String,
+    backtrace: Option<Backtrace>,
+  },
+  #[snafu(display("minimum frequency {min} Hz must be above 0 Hz with a log spectrum scale"))]
+  LogFrequency {
+    backtrace: Option<Backtrace>,
+    min: f32,
+  },
+  #[snafu(display("invalid mapping at {}:{line}", path.display()))]
+  Mapping {
+    backtrace: Option<Backtrace>,
+    line: usize,
+    path: PathBuf,
+    source: MappingError,
+  },
   #[snafu(display("failed to initialize MIDI input"))]
   MidiInputInit {
     backtrace: Option<Backtrace>,
@@ -115,6 +188,18 @@ pub(crate) enum Error {
     backtrace: Option<Backtrace>,
     source: midir::PortInfoError,
   },
+  #[snafu(display("playlist `{}` has no entries", path.display()))]
+  PlaylistEmpty {
+    backtrace: Option<Backtrace>,
+    path: PathBuf,
+  },
+  #[snafu(display("unknown program `{program}` at {}:{line}", path.display()))]
+  PlaylistProgram {
+    backtrace: Option<Backtrace>,
+    line: usize,
+    path: PathBuf,
+    program: String,
+  },
   #[snafu(display("failed to encode PNG at {}", path.display()))]
   PngEncode {
     backtrace: Option<Backtrace>,
@@ -151,6 +236,11 @@ pub(crate) enum Error {
     backtrace: Option<Backtrace>,
     source: winit::error::EventLoopError,
   },
+  #[snafu(display("failed to seek"))]
+  Seek {
+    backtrace: Option<Backtrace>,
+    source: rodio::source::SeekError,
+  },
   #[snafu(
     display(
       "more than one match for song: {}",
@@ -166,6 +256,11 @@ pub(crate) enum Error {
     backtrace: Option<Backtrace>,
     song: Regex,
   },
+  #[snafu(display("invalid song number `{input}`"))]
+  SongPick {
+    backtrace: Option<Backtrace>,
+    input: String,
+  },
   #[snafu(display("invalid song regex"))]
   SongRegex {
     backtrace: Option<Backtrace>,
@@ -176,6 +271,44 @@ pub(crate) enum Error {
     backtrace: Option<Backtrace>,
     source: walkdir::Error,
   },
+  #[snafu(display(
+    "more than one source matches `{name}`: {}",
+    candidates.join(", "),
+  ))]
+  SourceAmbiguous {
+    backtrace: Option<Backtrace>,
+    candidates: Vec<String>,
+    name: String,
+  },
+  #[snafu(display("no source matches `{name}`, candidates: {}", candidates.join(", ")))]
+  SourceMatch {
+    backtrace: Option<Backtrace>,
+    candidates: Vec<String>,
+    name: String,
+  },
+  #[snafu(display("spectrum floor {floor} dB must be below 0 dB"))]
+  SpectrumFloor {
+    backtrace: Option<Backtrace>,
+    floor: f32,
+  },
+  #[snafu(display("failed to read from stdin"))]
+  Stdin {
+    backtrace: Option<Backtrace>,
+    source: io::Error,
+  },
+  #[snafu(display("failed to write to stdout"))]
+  Stdout {
+    backtrace: Option<Backtrace>,
+    source: io::Error,
+  },
+  #[snafu(display(
+    "stems in `{}` are missing or do not share a channel count and sample rate",
+    path.display(),
+  ))]
+  StemFormat {
+    backtrace: Option<Backtrace>,
+    path: PathBuf,
+  },
   #[snafu(display("I/O error creating tempdir"))]
   TempdirIo {
     backtrace: Option<Backtrace>,
@@ -1,7 +1,27 @@
-use super::*;
+use midly::num::{u7, u14};
 
 #[derive(Clone, Copy, Debug)]
 pub(crate) enum Event {
-  Button(bool),
-  Encoder(Parameter),
+  ChannelAftertouch { pressure: u7 },
+  ControlChange { value: u7 },
+  ControlChange14 { value: u14 },
+  Note { press: bool, velocity: u7 },
+  PitchBend { value: u14 },
+  PolyAftertouch { pressure: u7 },
+  ProgramChange,
+}
+
+impl Event {
+  pub(crate) fn high(self) -> Option<bool> {
+    match self {
+      Self::ChannelAftertouch { pressure: value }
+      | Self::ControlChange { value }
+      | Self::Note {
+        velocity: value, ..
+      }
+      | Self::PolyAftertouch { pressure: value } => Some(value >= 64),
+      Self::ControlChange14 { value } | Self::PitchBend { value } => Some(value >= 8192),
+      Self::ProgramChange => None,
+    }
+  }
 }
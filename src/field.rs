@@ -1,15 +1,27 @@
 use super::*;
 
-#[derive(Clone, Copy, EnumIter, IntoStaticStr)]
+#[derive(Clone, Copy, Debug, EnumIter, IntoStaticStr, PartialEq)]
 #[repr(u32)]
 pub(crate) enum Field {
   All,
+  Beat,
   Bottom,
+  Chroma,
   Circle,
+  Correlation,
+  Flash,
   Frequencies,
+  Goniometer,
+  MidSide,
   None,
+  Onset,
+  Peaks,
   Samples,
+  Spectrogram,
+  Stereo,
   Top,
+  Upcoming,
+  Waveform,
   X,
 }
 
@@ -32,12 +44,24 @@ impl Field {
   pub(crate) fn icon(self) -> char {
     match self {
       Self::All => 'A',
+      Self::Beat => 'E',
       Self::Bottom => 'B',
+      Self::Chroma => 'K',
       Self::Circle => 'C',
+      Self::Correlation => 'R',
+      Self::Flash => 'L',
       Self::Frequencies => 'F',
+      Self::Goniometer => 'G',
+      Self::MidSide => 'M',
       Self::None => ' ',
+      Self::Onset => 'O',
+      Self::Peaks => 'P',
       Self::Samples => 'S',
+      Self::Spectrogram => 'Y',
+      Self::Stereo => 'Z',
       Self::Top => 'T',
+      Self::Upcoming => 'U',
+      Self::Waveform => 'W',
       Self::X => 'X',
     }
   }
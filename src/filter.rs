@@ -2,20 +2,26 @@ use super::*;
 
 #[derive(Clone)]
 pub(crate) struct Filter {
+  pub(crate) band: Option<Band>,
   pub(crate) color: Mat4f,
   pub(crate) coordinates: bool,
   pub(crate) field: Field,
+  pub(crate) hue: bool,
   pub(crate) position: Mat3f,
+  pub(crate) source: u32,
   pub(crate) wrap: bool,
 }
 
 impl Default for Filter {
   fn default() -> Self {
     Self {
+      band: None,
       color: Mat4f::identity(),
       coordinates: false,
       field: Field::default(),
+      hue: false,
       position: Mat3f::identity(),
+      source: 0,
       wrap: false,
     }
   }
@@ -0,0 +1,64 @@
+use super::*;
+
+#[derive(Default)]
+pub(crate) struct History {
+  frame: u64,
+  rows: VecDeque<Vec<f32>>,
+}
+
+impl History {
+  pub(crate) const ROWS: u32 = 512;
+
+  pub(crate) fn frame(&self) -> u64 {
+    self.frame
+  }
+
+  pub(crate) fn push(&mut self, row: impl IntoIterator<Item = f32>) {
+    let mut recycled = if self.rows.len() == Self::ROWS.into_usize() {
+      self.rows.pop_front().unwrap()
+    } else {
+      Vec::new()
+    };
+
+    recycled.clear();
+    recycled.extend(row);
+
+    self.rows.push_back(recycled);
+    self.frame += 1;
+  }
+
+  pub(crate) fn since(&self, frame: u64) -> impl Iterator<Item = (u64, &[f32])> {
+    let first = self.frame - self.rows.len() as u64;
+    self
+      .rows
+      .iter()
+      .enumerate()
+      .map(move |(i, row)| (first + i as u64, row.as_slice()))
+      .filter(move |(i, _)| *i >= frame)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn since() {
+    let mut history = History::default();
+
+    for i in 0..History::ROWS + 2 {
+      history.push([i as f32]);
+    }
+
+    assert_eq!(history.frame(), u64::from(History::ROWS) + 2);
+
+    assert_eq!(history.since(0).count(), History::ROWS.into_usize());
+
+    assert_eq!(
+      history.since(history.frame() - 1).collect::<Vec<_>>(),
+      [(history.frame() - 1, [(History::ROWS + 1) as f32].as_slice())],
+    );
+
+    assert_eq!(history.since(history.frame()).count(), 0);
+  }
+}
@@ -11,7 +11,7 @@ impl Hub {
     &self.messages
   }
 
-  pub(crate) fn new() -> Result<Self> {
+  pub(crate) fn new(mapping: &Mapping) -> Result<Self> {
     let messages = Arc::new(Mutex::new(VecDeque::new()));
 
     let mut connections = Vec::new();
@@ -20,13 +20,19 @@ impl Hub {
     for port in input.ports() {
       let name = input.port_name(&port).context(error::MidiPortInfo)?;
       let messages = messages.clone();
+      let port_name = Arc::<str>::from(name.as_str());
+      let mut controllers = Controllers::new(mapping.wide(&name));
       connections.push(
         midir::MidiInput::new(&format!("MIDI Port Input: {name}"))
           .context(error::MidiInputInit)?
           .connect(
             &port,
             &name,
-            move |_timestamp, event, ()| match Message::parse(event) {
+            move |_timestamp, event, ()| match Message::parse(
+              port_name.clone(),
+              event,
+              &mut controllers,
+            ) {
               Ok(message) => messages.lock().unwrap().push_back(message),
               Err(err) => log::warn!("MIDI event parse error: {err}"),
             },
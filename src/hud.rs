@@ -0,0 +1,298 @@
+use super::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(context(suffix(Error)))]
+pub(crate) enum HudError {
+  #[snafu(display("unknown HUD element `{element}`"))]
+  Element { element: String },
+  #[snafu(display("unknown HUD region `{region}`"))]
+  Region { region: String },
+  #[snafu(display("invalid HUD placement `{placement}`, expected `REGION=ELEMENT,...`"))]
+  Separator { placement: String },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub(crate) enum Element {
+  AutoGain,
+  Filters,
+  Fps,
+  Gain,
+  Latency,
+  Level,
+  Loudness,
+  Parameter,
+  Playhead,
+  Remaining,
+  Section,
+  Title,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub(crate) enum Region {
+  BottomLeft,
+  BottomRight,
+  TopLeft,
+  TopRight,
+}
+
+impl Region {
+  pub(crate) fn bottom(self) -> bool {
+    matches!(self, Self::BottomLeft | Self::BottomRight)
+  }
+
+  pub(crate) fn right(self) -> bool {
+    matches!(self, Self::BottomRight | Self::TopRight)
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Placement {
+  pub(crate) elements: Vec<Element>,
+  pub(crate) region: Region,
+}
+
+impl Placement {
+  pub(crate) fn defaults() -> Vec<Self> {
+    vec![
+      Self {
+        elements: vec![
+          Element::Fps,
+          Element::Parameter,
+          Element::Loudness,
+          Element::Playhead,
+          Element::Section,
+          Element::Latency,
+          Element::AutoGain,
+          Element::Filters,
+        ],
+        region: Region::BottomLeft,
+      },
+      Self {
+        elements: vec![Element::Title],
+        region: Region::TopLeft,
+      },
+      Self {
+        elements: vec![Element::Level, Element::Gain, Element::Remaining],
+        region: Region::TopRight,
+      },
+    ]
+  }
+}
+
+impl FromStr for Placement {
+  type Err = HudError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let (region, elements) = s.split_once('=').context(SeparatorError { placement: s })?;
+
+    Ok(Self {
+      elements: elements
+        .split(',')
+        .filter(|element| !element.is_empty())
+        .map(|element| {
+          Element::from_str(element, false)
+            .ok()
+            .context(ElementError { element })
+        })
+        .collect::<Result<Vec<Element>, HudError>>()?,
+      region: Region::from_str(region, false)
+        .ok()
+        .context(RegionError { region })?,
+    })
+  }
+}
+
+pub(crate) struct Hud<'a> {
+  pub(crate) analyzer: &'a Analyzer,
+  pub(crate) fps: Option<f32>,
+  pub(crate) input: Option<&'a Analyzer>,
+  pub(crate) options: &'a Options,
+  pub(crate) playhead: Option<&'a Playhead>,
+  pub(crate) state: &'a State,
+  pub(crate) title: Option<&'a str>,
+}
+
+impl Hud<'_> {
+  const METER: usize = 8;
+  const METER_FLOOR: f32 = -60.0;
+
+  fn item(&self, element: Element, items: &mut Vec<String>) {
+    match element {
+      Element::AutoGain => {
+        if self.options.auto_gain {
+          items.push(format!(
+            "A {:+.1}",
+            Renderer::auto_gain(self.options, self.analyzer)
+          ));
+        }
+      }
+      Element::Filters => {
+        for filter in &self.state.filters {
+          items.push(filter.icon().into());
+        }
+      }
+      Element::Fps => {
+        if let Some(fps) = self.fps {
+          items.push(format!("ƒ {}", fps.floor()));
+        }
+      }
+      Element::Gain => items.push(format!("G {:+.0} dB", self.state.db)),
+      Element::Latency => {
+        if let Some(latency) = self.options.latency {
+          items.push(format!("L {latency:+.0}"));
+        }
+      }
+      Element::Level => {
+        let analyzer = self.input.unwrap_or(self.analyzer);
+        let db = (20.0 * analyzer.rms().log10()).max(Self::METER_FLOOR);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let lit = ((1.0 - db / Self::METER_FLOOR) * Self::METER as f32).round() as usize;
+        items.push(format!(
+          "{}{} {db:.0}",
+          "▮".repeat(lit.min(Self::METER)),
+          "▯".repeat(Self::METER.saturating_sub(lit)),
+        ));
+      }
+      Element::Loudness => {
+        let loudness = self.analyzer.loudness();
+
+        if let Some(momentary) = loudness.momentary() {
+          items.push(format!("M {momentary:.1}"));
+        }
+
+        if let Some(short_term) = loudness.short_term() {
+          items.push(format!("S {short_term:.1}"));
+        }
+
+        if let Some(integrated) = loudness.integrated() {
+          items.push(format!("I {integrated:.1}"));
+        }
+
+        if loudness.true_peak() > 0.0 {
+          items.push(format!("TP {:.1}", 20.0 * loudness.true_peak().log10()));
+        }
+      }
+      Element::Parameter => {
+        let parameter = self.state.parameter.value();
+        items.push(if parameter >= 0 {
+          format!("+{parameter}")
+        } else {
+          parameter.to_string()
+        });
+      }
+      Element::Playhead => {
+        if let Some(playhead) = self.playhead {
+          items.push(playhead.to_string());
+        }
+      }
+      Element::Remaining => {
+        if let Some(remaining) = self.playhead.and_then(Playhead::remaining) {
+          items.push(remaining);
+        }
+      }
+      Element::Section => {
+        if let Some(section) = self.analyzer.section() {
+          items.push(section.name().into());
+        }
+      }
+      Element::Title => {
+        if let Some(title) = self.title {
+          items.push(title.into());
+        }
+      }
+    }
+  }
+
+  pub(crate) fn text(&self, elements: &[Element]) -> String {
+    let mut items = Vec::new();
+
+    for &element in elements {
+      self.item(element, &mut items);
+    }
+
+    items.join(" ")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse() {
+    assert_eq!(
+      "top-right=level,gain".parse::<Placement>().unwrap(),
+      Placement {
+        elements: vec![Element::Level, Element::Gain],
+        region: Region::TopRight,
+      },
+    );
+
+    assert!(matches!(
+      "top-right".parse::<Placement>(),
+      Err(HudError::Separator { .. })
+    ));
+
+    assert!(matches!(
+      "middle=level".parse::<Placement>(),
+      Err(HudError::Region { .. })
+    ));
+
+    assert!(matches!(
+      "top-left=level,volume".parse::<Placement>(),
+      Err(HudError::Element { .. })
+    ));
+  }
+
+  #[test]
+  fn text() {
+    let options = Options::default();
+    let analyzer = Analyzer::new(&options);
+    let state = State::default().db(6);
+    let playhead = Playhead {
+      duration: Some(Duration::from_secs(225)),
+      looping: false,
+      paused: false,
+      position: Duration::from_secs(83),
+    };
+
+    let mut hud = Hud {
+      analyzer: &analyzer,
+      fps: Some(59.9),
+      input: None,
+      options: &options,
+      playhead: Some(&playhead),
+      state: &state,
+      title: Some("Artist — Title"),
+    };
+
+    assert_eq!(
+      hud.text(&[
+        Element::Title,
+        Element::Fps,
+        Element::Remaining,
+        Element::Gain,
+        Element::Level,
+        Element::Latency,
+      ]),
+      "Artist — Title ƒ 59 -2:22 G +6 dB ▯▯▯▯▯▯▯▯ -60",
+    );
+
+    let mut input = Analyzer::new(&options);
+
+    input.update(
+      &mut Samples::new(
+        1,
+        48_000,
+        Generator::new(Signal::Sine { frequency: 440.0 })
+          .take(48_000)
+          .collect(),
+      ),
+      &state,
+    );
+
+    hud.input = Some(&input);
+
+    assert_eq!(hud.text(&[Element::Level]), "▮▮▮▮▮▮▮▮ -3");
+  }
+}
@@ -8,6 +8,12 @@ impl IntoUsize for u32 {
   }
 }
 
+impl IntoUsize for u64 {
+  fn into_usize(self) -> usize {
+    self.try_into().unwrap()
+  }
+}
+
 impl IntoUsize for f32 {
   fn into_usize(self) -> usize {
     #![allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
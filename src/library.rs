@@ -0,0 +1,288 @@
+use super::*;
+
+pub(crate) struct Library {
+  songs: Vec<Song>,
+}
+
+impl Library {
+  pub(crate) const EXTENSIONS: &[&str] = &[
+    "aac", "aif", "aiff", "caf", "flac", "m4a", "mka", "mp3", "mp4", "oga", "ogg", "wav", "webm",
+  ];
+  const HEADER: &str = "x-library 1";
+
+  fn decode(text: &str) -> HashMap<PathBuf, Song> {
+    let mut lines = text.lines();
+
+    if lines.next() != Some(Self::HEADER) {
+      return HashMap::new();
+    }
+
+    lines
+      .filter_map(Song::decode)
+      .map(|song| (song.path.clone(), song))
+      .collect()
+  }
+
+  fn encode<'a>(songs: impl IntoIterator<Item = &'a Song>) -> String {
+    let mut text = format!("{}\n", Self::HEADER);
+
+    for song in songs {
+      if let Some(line) = song.encode() {
+        text.push_str(&line);
+        text.push('\n');
+      }
+    }
+
+    text
+  }
+
+  pub(crate) fn find(&self, song: &str) -> Result<PathBuf> {
+    let mut matches = self
+      .search(song)?
+      .into_iter()
+      .map(|song| song.path.clone())
+      .collect::<Vec<PathBuf>>();
+
+    if matches.len() > 1 {
+      return Err(error::SongAmbiguous { matches }.build());
+    }
+
+    matches.pop().context(error::SongMatch {
+      song: Self::regex(song)?,
+    })
+  }
+
+  pub(crate) fn load(options: &Options) -> Result<Self> {
+    let roots = if options.library.is_empty() {
+      vec![
+        dirs::home_dir()
+          .context(error::Home)?
+          .join("Music/Music/Media.localized/Music"),
+      ]
+    } else {
+      options.library.clone()
+    };
+
+    let index = dirs::cache_dir()
+      .context(error::CacheDir)?
+      .join("x")
+      .join("library");
+
+    let mut cached = fs::read_to_string(&index)
+      .map(|text| Self::decode(&text))
+      .unwrap_or_default();
+
+    let (songs, mut changed) = Self::scan(&roots, &mut cached)?;
+
+    // entries left under the scanned roots are files that no longer exist,
+    // entries under other roots are kept for the next launch that uses them
+    cached.retain(|path, _| {
+      let stale = roots.iter().any(|root| path.starts_with(root));
+      changed |= stale;
+      !stale
+    });
+
+    let library = Self { songs };
+
+    if changed {
+      let parent = index.parent().unwrap();
+      fs::create_dir_all(parent).context(error::FilesystemIo { path: parent })?;
+
+      fs::write(
+        &index,
+        Self::encode(library.songs.iter().chain(cached.values())),
+      )
+      .context(error::FilesystemIo { path: &index })?;
+
+      log::info!(
+        "indexed {} in {}",
+        Tally("song", library.songs.len()),
+        index.display(),
+      );
+    }
+
+    Ok(library)
+  }
+
+  fn regex(song: &str) -> Result<Regex> {
+    RegexBuilder::new(song)
+      .case_insensitive(true)
+      .build()
+      .context(error::SongRegex)
+  }
+
+  fn scan(roots: &[PathBuf], cached: &mut HashMap<PathBuf, Song>) -> Result<(Vec<Song>, bool)> {
+    let mut songs = Vec::new();
+    let mut changed = false;
+
+    for root in roots {
+      for entry in WalkDir::new(root) {
+        let entry = entry.context(error::SongWalk)?;
+
+        let path = entry.path();
+
+        if entry.file_type().is_dir()
+          || !path
+            .extension()
+            .and_then(OsStr::to_str)
+            .is_some_and(|extension| Self::EXTENSIONS.contains(&extension.to_lowercase().as_str()))
+        {
+          continue;
+        }
+
+        let modified = entry
+          .metadata()
+          .ok()
+          .and_then(|metadata| metadata.modified().ok())
+          .and_then(|modified| modified.duration_since(SystemTime::UNIX_EPOCH).ok())
+          .map_or(0, |modified| modified.as_secs());
+
+        if let Some(song) = cached.remove(path)
+          && song.modified == modified
+        {
+          songs.push(song);
+          continue;
+        }
+
+        let Some(name) = path
+          .strip_prefix(root)
+          .unwrap()
+          .with_extension("")
+          .to_str()
+          .map(str::to_owned)
+        else {
+          continue;
+        };
+
+        let song = Song::probe(path, name.clone(), modified).unwrap_or_else(|| {
+          log::warn!("failed to read {}", path.display());
+          Song {
+            album: String::new(),
+            artist: String::new(),
+            duration: None,
+            modified,
+            name,
+            path: path.into(),
+            title: String::new(),
+          }
+        });
+
+        changed |= song.encode().is_some();
+
+        songs.push(song);
+      }
+    }
+
+    Ok((songs, changed))
+  }
+
+  pub(crate) fn search(&self, song: &str) -> Result<Vec<&Song>> {
+    let song = Self::regex(song)?;
+
+    let mut matches = self
+      .songs
+      .iter()
+      .filter(|candidate| {
+        candidate
+          .haystacks()
+          .iter()
+          .any(|haystack| song.is_match(haystack))
+      })
+      .collect::<Vec<&Song>>();
+
+    matches.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(matches)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn song(name: &str, artist: &str) -> Song {
+    Song {
+      album: String::new(),
+      artist: artist.into(),
+      duration: None,
+      modified: 0,
+      name: name.into(),
+      path: format!("/music/{name}.flac").into(),
+      title: String::new(),
+    }
+  }
+
+  #[test]
+  fn find() {
+    let library = Library {
+      songs: vec![song("a/first", "Foo"), song("b/second", "Bar")],
+    };
+
+    assert_eq!(
+      library.find("FIRST").unwrap(),
+      Path::new("/music/a/first.flac")
+    );
+    assert_eq!(
+      library.find("^bar$").unwrap(),
+      Path::new("/music/b/second.flac")
+    );
+
+    assert!(matches!(
+      library.find("s"),
+      Err(Error::SongAmbiguous { .. })
+    ));
+
+    assert!(matches!(
+      library.find("third"),
+      Err(Error::SongMatch { .. })
+    ));
+  }
+
+  #[test]
+  fn scan() {
+    let tempdir = tempfile::tempdir().unwrap();
+
+    let root = tempdir.path().to_owned();
+
+    fs::create_dir(root.join("album")).unwrap();
+    fs::write(root.join("album/broken.mp3"), "not audio").unwrap();
+    fs::write(root.join("notes.txt"), "").unwrap();
+
+    let roots = [root.clone()];
+
+    let mut cached = HashMap::new();
+
+    let (songs, changed) = Library::scan(&roots, &mut cached).unwrap();
+
+    assert!(changed);
+    assert_eq!(songs.len(), 1);
+    assert_eq!(songs[0].name, "album/broken");
+
+    let mut cached = Library::decode(&Library::encode(&songs));
+
+    cached
+      .get_mut(&root.join("album/broken.mp3"))
+      .unwrap()
+      .title = "cached".into();
+
+    let (songs, changed) = Library::scan(&roots, &mut cached).unwrap();
+
+    assert!(!changed);
+    assert!(cached.is_empty());
+    assert_eq!(songs[0].title, "cached");
+  }
+
+  #[test]
+  fn round_trip() {
+    let library = Library {
+      songs: vec![song("a/first", "Foo"), song("b/second", "Bar")],
+    };
+
+    let decoded = Library::decode(&Library::encode(&library.songs));
+
+    assert_eq!(decoded.len(), 2);
+    assert_eq!(decoded[Path::new("/music/a/first.flac")], library.songs[0]);
+
+    assert!(Library::decode("bogus\n").is_empty());
+  }
+}
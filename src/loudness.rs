@@ -0,0 +1,198 @@
+use super::*;
+
+#[derive(Default)]
+pub(crate) struct Loudness {
+  count: usize,
+  filters: Vec<[Biquad; 2]>,
+  histogram: Vec<(f64, u64)>,
+  history: Vec<[f32; 3]>,
+  power: f32,
+  sample_rate: u32,
+  steps: VecDeque<f32>,
+  true_peak: f32,
+}
+
+impl Loudness {
+  const ABSOLUTE_GATE: f32 = -70.0;
+  // 0.1 lu bins from the absolute gate up to +10 lufs
+  const HISTOGRAM_BINS: usize = 800;
+  const HISTOGRAM_RESOLUTION: f32 = 10.0;
+  const MOMENTARY_STEPS: usize = 4;
+  const RELATIVE_GATE: f32 = -10.0;
+  const SHORT_TERM_STEPS: usize = 30;
+  const STEPS_PER_SECOND: u32 = 10;
+
+  fn bin(lufs: f32) -> usize {
+    ((lufs - Self::ABSOLUTE_GATE) * Self::HISTOGRAM_RESOLUTION)
+      .into_usize()
+      .min(Self::HISTOGRAM_BINS - 1)
+  }
+
+  #[allow(clippy::cast_possible_truncation)]
+  pub(crate) fn integrated(&self) -> Option<f32> {
+    let gated = |bins: &[(f64, u64)]| {
+      let (power, blocks) = bins.iter().fold((0.0, 0), |(power, blocks), &(sum, n)| {
+        (power + sum, blocks + n)
+      });
+      Self::lufs(if blocks == 0 {
+        0.0
+      } else {
+        (power / blocks as f64) as f32
+      })
+    };
+
+    let threshold = gated(&self.histogram)? + Self::RELATIVE_GATE;
+
+    gated(&self.histogram[Self::bin(threshold)..])
+  }
+
+  fn lufs(power: f32) -> Option<f32> {
+    (power > 0.0).then(|| -0.691 + 10.0 * power.log10())
+  }
+
+  fn mean(values: impl Iterator<Item = f32>) -> f32 {
+    let (sum, n) = values.fold((0.0, 0), |(sum, n), value| (sum + value, n + 1));
+    if n == 0 { 0.0 } else { sum / n as f32 }
+  }
+
+  pub(crate) fn momentary(&self) -> Option<f32> {
+    Self::lufs(Self::mean(
+      self
+        .steps
+        .iter()
+        .copied()
+        .skip(self.steps.len().saturating_sub(Self::MOMENTARY_STEPS)),
+    ))
+  }
+
+  pub(crate) fn reset(&mut self) {
+    self.count = 0;
+    self.filters.clear();
+    self.histogram = vec![(0.0, 0); Self::HISTOGRAM_BINS];
+    self.history.clear();
+    self.power = 0.0;
+    self.steps.clear();
+    self.true_peak = 0.0;
+  }
+
+  pub(crate) fn short_term(&self) -> Option<f32> {
+    Self::lufs(Self::mean(self.steps.iter().copied()))
+  }
+
+  pub(crate) fn true_peak(&self) -> f32 {
+    self.true_peak
+  }
+
+  pub(crate) fn update(&mut self, samples: &[f32], channels: usize, sample_rate: u32) {
+    if sample_rate != self.sample_rate || channels != self.filters.len() {
+      self.reset();
+      self.sample_rate = sample_rate;
+      let rate = sample_rate as f32;
+      self.filters = vec![
+        [
+          Biquad::high_shelf(rate, 1_681.974_5, 0.707_175_24, 3.999_843_9),
+          Biquad::high_pass(rate, 38.135_47, 0.500_327_04),
+        ];
+        channels
+      ];
+      self.history = vec![[0.0; 3]; channels];
+    }
+
+    let step = (sample_rate / Self::STEPS_PER_SECOND).max(1).into_usize();
+
+    for frame in samples.chunks_exact(channels) {
+      for ((&sample, [shelf, pass]), history) in
+        frame.iter().zip(&mut self.filters).zip(&mut self.history)
+      {
+        let weighted = pass.process(shelf.process(sample));
+        self.power += weighted * weighted;
+
+        let [p0, p1, p2] = *history;
+        for t in [0.25, 0.5, 0.75] {
+          self.true_peak = self.true_peak.max(catmull_rom(p0, p1, p2, sample, t).abs());
+        }
+        self.true_peak = self.true_peak.max(sample.abs());
+        *history = [p1, p2, sample];
+      }
+
+      self.count += 1;
+
+      if self.count == step {
+        self.steps.push_back(self.power / step as f32);
+        self.power = 0.0;
+        self.count = 0;
+
+        if self.steps.len() > Self::SHORT_TERM_STEPS {
+          self.steps.pop_front();
+        }
+
+        if self.steps.len() >= Self::MOMENTARY_STEPS {
+          let block = Self::mean(
+            self
+              .steps
+              .iter()
+              .copied()
+              .skip(self.steps.len() - Self::MOMENTARY_STEPS),
+          );
+
+          if let Some(lufs) = Self::lufs(block).filter(|&lufs| lufs >= Self::ABSOLUTE_GATE) {
+            let (power, blocks) = &mut self.histogram[Self::bin(lufs)];
+            *power += f64::from(block);
+            *blocks += 1;
+          }
+        }
+      }
+    }
+  }
+}
+
+fn catmull_rom(p0: f32, p1: f32, p2: f32, p3: f32, t: f32) -> f32 {
+  0.5
+    * (2.0 * p1
+      + (p2 - p0) * t
+      + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t * t
+      + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t * t * t)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn stereo_sine() {
+    let amplitude = 10f32.powf(-23.0 / 20.0);
+
+    let samples = (0..48_000 * 5)
+      .map(|i| amplitude * (TAU * 1000.0 * i as f32 / 48_000.0).sin())
+      .flat_map(|sample| [sample, sample])
+      .collect::<Vec<f32>>();
+
+    let mut loudness = Loudness::default();
+
+    for chunk in samples.chunks(1000) {
+      loudness.update(chunk, 2, 48_000);
+    }
+
+    for lufs in [
+      loudness.integrated(),
+      loudness.momentary(),
+      loudness.short_term(),
+    ] {
+      let lufs = lufs.unwrap();
+      assert!((lufs + 23.0).abs() < 0.1, "lufs: {lufs}");
+    }
+
+    assert!((loudness.true_peak() - amplitude).abs() < 0.01 * amplitude);
+  }
+
+  #[test]
+  fn silence() {
+    let mut loudness = Loudness::default();
+
+    loudness.update(&vec![0.0; 96_000], 2, 48_000);
+
+    assert_eq!(loudness.integrated(), None);
+    assert_eq!(loudness.short_term(), None);
+    assert_eq!(loudness.true_peak(), 0.0);
+  }
+}
@@ -1,12 +1,68 @@
 use {
   self::{
-    analyzer::Analyzer, app::App, arguments::Arguments, bindings::Bindings, device::Device,
-    error::Error, event::Event, field::Field, filter::Filter, format::Format, frame::Frame,
-    hub::Hub, image::Image, input::Input, into_usize::IntoUsize, message::Message,
-    options::Options, parameter::Parameter, program::Program, recorder::Recorder,
-    renderer::Renderer, shared::Shared, state::State, stream::Stream, subcommand::Subcommand,
-    tally::Tally, target::Target, templates::ShaderWgsl, text::Text, tiling::Tiling, track::Track,
+    action::{Action, ActionError},
+    analysis::Analysis,
+    analyzer::Analyzer,
+    app::App,
+    arguments::Arguments,
+    autopilot::Autopilot,
+    band::Band,
+    bindings::Bindings,
+    biquad::Biquad,
+    channel::Channel,
+    chroma::Chroma,
+    click::Click,
+    controllers::Controllers,
+    delay::Delay,
+    envelope::Envelope,
+    error::Error,
+    event::Event,
+    field::Field,
+    filter::Filter,
+    format::Format,
+    frame::Frame,
+    history::History,
+    hub::Hub,
+    hud::{Hud, Placement, Region},
+    image::Image,
+    input::Input,
+    into_usize::IntoUsize,
+    library::Library,
+    loudness::Loudness,
+    mapping::{Gesture, Mapping, MappingError},
+    measurement::Measurement,
+    message::Message,
+    mix::Mix,
+    options::Options,
+    output::Output,
+    parameter::Parameter,
+    peak::Peak,
+    playhead::Playhead,
+    playlist::{Entry, Playlist},
+    program::Program,
+    quoted::Quoted,
+    recorder::Recorder,
+    renderer::Renderer,
+    samples::Samples,
+    scale::Scale,
+    section::Section,
+    shared::Shared,
+    signal::{Generator, Signal},
+    snapshot::Snapshot,
+    song::Song,
+    state::State,
+    stream::Stream,
+    subcommand::Subcommand,
+    tally::Tally,
+    target::Target,
+    templates::ShaderWgsl,
+    text::Text,
+    tiling::Tiling,
+    timestamp::Timestamp,
+    track::Track,
+    transport::Transport,
     uniforms::Uniforms,
+    window_function::WindowFunction,
   },
   boilerplate::Boilerplate,
   clap::{Parser, ValueEnum},
@@ -16,29 +72,38 @@ use {
   rodio::{
     Decoder, OutputStream, Sink, Source,
     cpal::{
-      self, Sample, SampleFormat, StreamConfig, SupportedBufferSize, SupportedStreamConfig,
+      self, SampleFormat, StreamConfig, SupportedBufferSize, SupportedStreamConfig,
       SupportedStreamConfigRange,
       traits::{DeviceTrait, HostTrait, StreamTrait},
     },
+    mixer::Mixer,
+    source::SeekError,
   },
-  rustfft::{FftPlanner, num_complex::Complex},
+  rustfft::{Fft, FftPlanner, num_complex::Complex},
   snafu::{ErrorCompat, IntoError, OptionExt, ResultExt, Snafu},
   std::{
+    array,
     backtrace::{Backtrace, BacktraceStatus},
     borrow::Cow,
-    collections::VecDeque,
+    collections::{HashMap, VecDeque},
+    f32::consts::{PI, TAU},
+    ffi::OsStr,
     fmt::{self, Display, Formatter, Write},
     fs::{self, File},
     io::{self, BufReader, BufWriter},
-    num,
+    iter, mem, num,
     ops::{Add, AddAssign, SubAssign},
     path::{Path, PathBuf},
     process::{self, Command, ExitStatus},
     str::FromStr,
-    sync::{Arc, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard, mpsc},
-    time::Instant,
+    sync::{
+      Arc, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard,
+      atomic::{self, AtomicBool},
+      mpsc,
+    },
+    time::{Duration, Instant, SystemTime},
   },
-  strum::{EnumIter, IntoEnumIterator, IntoStaticStr},
+  strum::{EnumCount, EnumIter, IntoEnumIterator, IntoStaticStr},
   tempfile::TempDir,
   vello::{kurbo, peniko},
   walkdir::WalkDir,
@@ -73,28 +138,56 @@ macro_rules! label {
   };
 }
 
+mod action;
+mod analysis;
 mod analyzer;
 mod app;
 mod arguments;
+mod autopilot;
+mod band;
 mod bindings;
-mod device;
+mod biquad;
+mod channel;
+mod chroma;
+mod click;
+mod controllers;
+mod delay;
+mod envelope;
 mod error;
 mod event;
 mod field;
 mod filter;
 mod format;
 mod frame;
+mod history;
 mod hub;
+mod hud;
 mod image;
 mod input;
 mod into_usize;
+mod library;
+mod loudness;
+mod mapping;
+mod measurement;
 mod message;
+mod mix;
 mod options;
+mod output;
 mod parameter;
+mod peak;
+mod playhead;
+mod playlist;
 mod program;
+mod quoted;
 mod recorder;
 mod renderer;
+mod samples;
+mod scale;
+mod section;
 mod shared;
+mod signal;
+mod snapshot;
+mod song;
 mod state;
 mod stream;
 mod subcommand;
@@ -103,8 +196,11 @@ mod target;
 mod templates;
 mod text;
 mod tiling;
+mod timestamp;
 mod track;
+mod transport;
 mod uniforms;
+mod window_function;
 
 const KIB: usize = 1 << 10;
 const MIB: usize = KIB << 10;
@@ -0,0 +1,658 @@
+use super::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(context(suffix(Error)))]
+pub(crate) enum MappingError {
+  #[snafu(transparent)]
+  Action { source: ActionError },
+  #[snafu(display("`{statement}` expects at least {expected} argument(s)"))]
+  Arguments {
+    expected: usize,
+    statement: &'static str,
+  },
+  #[snafu(display("invalid channel `{channel}`, expected 1 through 16"))]
+  Channel { channel: String },
+  #[snafu(display("unknown control `{name}`"))]
+  Control { name: String },
+  #[snafu(display("control `{name}` already defined"))]
+  Duplicate { name: String },
+  #[snafu(display("unknown message `{message}`"))]
+  Kind { message: String },
+  #[snafu(display("invalid number `{number}`, expected 0 through 127"))]
+  Number { number: String },
+  #[snafu(display("unknown statement `{statement}`, expected `bind` or `control`"))]
+  Statement { statement: String },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Gesture {
+  Press,
+  Release,
+  Turn(Parameter),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub(crate) enum Kind {
+  Aftertouch,
+  Cc,
+  Cc14,
+  Note,
+  PitchBend,
+  PolyAftertouch,
+  Program,
+  Velocity,
+}
+
+impl Kind {
+  fn new(event: Event, continuous: bool) -> Self {
+    match event {
+      Event::ChannelAftertouch { .. } => Self::Aftertouch,
+      Event::ControlChange { .. } => Self::Cc,
+      Event::ControlChange14 { .. } => Self::Cc14,
+      Event::Note { .. } => {
+        if continuous {
+          Self::Velocity
+        } else {
+          Self::Note
+        }
+      }
+      Event::PitchBend { .. } => Self::PitchBend,
+      Event::PolyAftertouch { .. } => Self::PolyAftertouch,
+      Event::ProgramChange => Self::Program,
+    }
+  }
+}
+
+#[derive(Debug, PartialEq)]
+struct Control {
+  channel: u8,
+  high: bool,
+  kind: Kind,
+  name: String,
+  number: u8,
+  port: Option<String>,
+}
+
+impl Display for Control {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "control {} {} {} {}",
+      self.name,
+      self.channel + 1,
+      self.kind.to_possible_value().unwrap().get_name(),
+      self.number,
+    )?;
+
+    if let Some(port) = &self.port {
+      write!(f, " {port}")?;
+    }
+
+    Ok(())
+  }
+}
+
+impl Control {
+  fn gesture(&self, message: &Message) -> Option<Gesture> {
+    if message.channel != self.channel
+      || message.number != self.number
+      || !self.matches(&message.port)
+    {
+      return None;
+    }
+
+    match (self.kind, message.event) {
+      (Kind::Aftertouch, Event::ChannelAftertouch { pressure })
+      | (Kind::PolyAftertouch, Event::PolyAftertouch { pressure }) => {
+        Some(Gesture::Turn(pressure.into()))
+      }
+      (Kind::Cc, Event::ControlChange { value })
+      | (
+        Kind::Velocity,
+        Event::Note {
+          velocity: value, ..
+        },
+      ) => Some(Gesture::Turn(value.into())),
+      (Kind::Cc14, Event::ControlChange14 { value })
+      | (Kind::PitchBend, Event::PitchBend { value }) => Some(Gesture::Turn(value.into())),
+      (Kind::Note, Event::Note { press, .. }) => Some(if press {
+        Gesture::Press
+      } else {
+        Gesture::Release
+      }),
+      (Kind::Program, Event::ProgramChange) => Some(Gesture::Press),
+      _ => None,
+    }
+  }
+
+  fn matches(&self, port: &str) -> bool {
+    self
+      .port
+      .as_ref()
+      .is_none_or(|pattern| port.to_lowercase().contains(&pattern.to_lowercase()))
+  }
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct Mapping {
+  bindings: Vec<(usize, Action)>,
+  controls: Vec<Control>,
+}
+
+impl Mapping {
+  pub(crate) const DEFAULT: &str = include_str!("../mappings/default.mapping");
+
+  pub(crate) fn actions(&mut self, message: &Message) -> Vec<(Action, Gesture)> {
+    let mut actions = Vec::new();
+
+    for (i, control) in self.controls.iter_mut().enumerate() {
+      let Some(gesture) = control.gesture(message) else {
+        continue;
+      };
+
+      // discrete actions bound to value controls, like buttons that send
+      // control changes, fire when the value crosses the midpoint
+      let edge = if let Gesture::Turn(_) = gesture {
+        let high = message.event.high().unwrap_or_default();
+        let previous = mem::replace(&mut control.high, high);
+        match (previous, high) {
+          (false, true) => Some(Gesture::Press),
+          (true, false) => Some(Gesture::Release),
+          _ => None,
+        }
+      } else {
+        Some(gesture)
+      };
+
+      for (_, action) in self.bindings.iter().filter(|(control, _)| *control == i) {
+        if let Some(gesture) = if action.continuous() {
+          Some(gesture)
+        } else {
+          edge
+        } {
+          actions.push((action.clone(), gesture));
+        }
+      }
+    }
+
+    actions
+  }
+
+  fn bind(&mut self, words: &[&str]) -> Result<(), MappingError> {
+    let [name, action @ ..] = words else {
+      return Err(
+        ArgumentsError {
+          expected: 2usize,
+          statement: "bind",
+        }
+        .build(),
+      );
+    };
+
+    let control = self
+      .controls
+      .iter()
+      .position(|control| control.name == *name)
+      .context(ControlError { name: *name })?;
+
+    self.bindings.push((control, action.join(" ").parse()?));
+
+    Ok(())
+  }
+
+  fn control(&mut self, words: &[&str]) -> Result<(), MappingError> {
+    let [name, channel, kind, number, port @ ..] = words else {
+      return Err(
+        ArgumentsError {
+          expected: 4usize,
+          statement: "control",
+        }
+        .build(),
+      );
+    };
+
+    if self.controls.iter().any(|control| control.name == *name) {
+      return Err(DuplicateError { name: *name }.build());
+    }
+
+    let channel = channel
+      .parse::<u8>()
+      .ok()
+      .filter(|channel| (1..=16).contains(channel))
+      .context(ChannelError { channel: *channel })?;
+
+    self.controls.push(Control {
+      channel: channel - 1,
+      high: false,
+      kind: Kind::from_str(kind, false)
+        .ok()
+        .context(KindError { message: *kind })?,
+      name: (*name).into(),
+      number: number
+        .parse::<u8>()
+        .ok()
+        .filter(|number| *number < 128)
+        .context(NumberError { number: *number })?,
+      port: (!port.is_empty()).then(|| port.join(" ")),
+    });
+
+    Ok(())
+  }
+
+  pub(crate) fn learn(&mut self, action: Action, message: &Message) -> Result<String> {
+    let kind = Kind::new(message.event, action.continuous());
+
+    if kind == Kind::Program && action.continuous() {
+      return Err(
+        error::LearnProgram {
+          action: action.to_string(),
+        }
+        .build(),
+      );
+    }
+
+    let mut text = String::new();
+
+    let control = if let Some(control) = self.controls.iter().position(|control| {
+      control.channel == message.channel
+        && control.kind == kind
+        && control.number == message.number
+        && control.matches(&message.port)
+    }) {
+      control
+    } else {
+      let base = format!(
+        "{}-{}-{}",
+        kind.to_possible_value().unwrap().get_name(),
+        message.channel + 1,
+        message.number,
+      );
+
+      let name = (1..=self.controls.len() + 1)
+        .map(|i| {
+          if i == 1 {
+            base.clone()
+          } else {
+            format!("{base}-{i}")
+          }
+        })
+        .find(|name| self.controls.iter().all(|control| control.name != *name))
+        .unwrap();
+
+      let control = Control {
+        channel: message.channel,
+        high: false,
+        kind,
+        name,
+        number: message.number,
+        port: Some(message.port.to_string()),
+      };
+
+      writeln!(text, "{control}").unwrap();
+
+      self.controls.push(control);
+
+      self.controls.len() - 1
+    };
+
+    if !self
+      .bindings
+      .iter()
+      .any(|binding| binding.0 == control && binding.1 == action)
+    {
+      writeln!(text, "bind {} {action}", self.controls[control].name).unwrap();
+      self.bindings.push((control, action));
+    }
+
+    Ok(text)
+  }
+
+  pub(crate) fn load(options: &Options) -> Result<Self> {
+    match &options.mapping {
+      Some(path) => {
+        let text = fs::read_to_string(path).context(error::FilesystemIo { path })?;
+        Self::parse(path, &text)
+      }
+      None => Self::parse(Path::new("default.mapping"), Self::DEFAULT),
+    }
+  }
+
+  pub(crate) fn parse(path: &Path, text: &str) -> Result<Self> {
+    let mut mapping = Self {
+      bindings: Vec::new(),
+      controls: Vec::new(),
+    };
+
+    for (i, line) in text.lines().enumerate() {
+      let words = line
+        .split('#')
+        .next()
+        .unwrap_or_default()
+        .split_whitespace()
+        .collect::<Vec<&str>>();
+
+      let Some((statement, words)) = words.split_first() else {
+        continue;
+      };
+
+      match *statement {
+        "bind" => mapping.bind(words),
+        "control" => mapping.control(words),
+        _ => Err(
+          StatementError {
+            statement: *statement,
+          }
+          .build(),
+        ),
+      }
+      .context(error::Mapping { line: i + 1, path })?;
+    }
+
+    Ok(mapping)
+  }
+
+  pub(crate) fn sources(&self) -> impl Iterator<Item = &str> {
+    self.bindings.iter().filter_map(|(_, action)| match action {
+      Action::Push(_, Some(source)) | Action::Source(source) => Some(source.as_str()),
+      _ => None,
+    })
+  }
+
+  pub(crate) fn wide(&self, port: &str) -> Vec<(u8, u8)> {
+    self
+      .controls
+      .iter()
+      .filter(|control| control.kind == Kind::Cc14 && control.matches(port))
+      .map(|control| (control.channel, control.number))
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn message(port: &str, channel: u8, number: u8, press: bool, velocity: u8) -> Message {
+    Message {
+      channel,
+      event: Event::Note {
+        press,
+        velocity: velocity.into(),
+      },
+      number,
+      port: port.into(),
+    }
+  }
+
+  #[test]
+  fn default() {
+    let mut mapping = Mapping::load(&Options::default()).unwrap();
+
+    assert_eq!(
+      mapping.actions(&message("Spectra", 2, 51, true, 127)),
+      [(Action::Push(Field::Circle, None), Gesture::Press)],
+    );
+
+    assert_eq!(
+      mapping.actions(&message("Twister", 0, 1, true, 127)),
+      [(Action::Db, Gesture::Turn(Parameter::from(63)))],
+    );
+
+    assert!(
+      mapping
+        .actions(&message("Twister", 5, 1, true, 0))
+        .is_empty()
+    );
+  }
+
+  #[test]
+  fn edges() {
+    let mut mapping = Mapping::parse(
+      Path::new("test.mapping"),
+      "control button 1 cc 20\nbind button pop\nbind button parameter\n",
+    )
+    .unwrap();
+
+    let mut cc = |value: u8| {
+      mapping.actions(&Message {
+        channel: 0,
+        event: Event::ControlChange {
+          value: value.into(),
+        },
+        number: 20,
+        port: "port".into(),
+      })
+    };
+
+    assert_eq!(
+      cc(127),
+      [
+        (Action::Pop, Gesture::Press),
+        (Action::Parameter, Gesture::Turn(Parameter::from(63))),
+      ],
+    );
+
+    assert_eq!(
+      cc(100),
+      [(Action::Parameter, Gesture::Turn(Parameter::from(36)))],
+    );
+
+    assert_eq!(
+      cc(0),
+      [
+        (Action::Pop, Gesture::Release),
+        (Action::Parameter, Gesture::Turn(Parameter::from(-64))),
+      ],
+    );
+
+    assert_eq!(cc(64)[0], (Action::Pop, Gesture::Press));
+  }
+
+  #[test]
+  fn errors() {
+    #[track_caller]
+    fn case(text: &str, line: usize, expected: &str) {
+      let err = Mapping::parse(Path::new("test.mapping"), text).unwrap_err();
+      assert_eq!(
+        err.to_string(),
+        format!("invalid mapping at test.mapping:{line}")
+      );
+      assert_eq!(err.iter_chain().nth(1).unwrap().to_string(), expected);
+    }
+
+    case(
+      "frob",
+      1,
+      "unknown statement `frob`, expected `bind` or `control`",
+    );
+    case(
+      "# comment\n\ncontrol a 17 note 0",
+      3,
+      "invalid channel `17`, expected 1 through 16",
+    );
+    case(
+      "control a 1 note 128",
+      1,
+      "invalid number `128`, expected 0 through 127",
+    );
+    case("control a 1 sysex 0", 1, "unknown message `sysex`");
+    case(
+      "control a 1 note 0\ncontrol a 1 note 1",
+      2,
+      "control `a` already defined",
+    );
+    case("bind a pop", 1, "unknown control `a`");
+    case("control a 1 note 0\nbind a fly", 2, "unknown action `fly`");
+    case(
+      "control a 1 note",
+      1,
+      "`control` expects at least 4 argument(s)",
+    );
+  }
+
+  #[test]
+  fn learn() {
+    let path = Path::new("test.mapping");
+
+    let mut text = String::from("control pad 1 note 36\nbind pad pop\n");
+
+    let mut mapping = Mapping::parse(path, &text).unwrap();
+
+    let pad = message("Launchpad", 0, 36, true, 127);
+    let knob = message("Launchpad", 0, 21, true, 64);
+
+    let learned = mapping
+      .learn(Action::Push(Field::Circle, None), &pad)
+      .unwrap();
+    assert_eq!(learned, "bind pad push circle\n");
+    text.push_str(&learned);
+
+    assert_eq!(
+      mapping.actions(&pad),
+      [
+        (Action::Pop, Gesture::Press),
+        (Action::Push(Field::Circle, None), Gesture::Press),
+      ],
+    );
+
+    let learned = mapping.learn(Action::Db, &knob).unwrap();
+    assert_eq!(
+      learned,
+      "control velocity-1-21 1 velocity 21 Launchpad\nbind velocity-1-21 db\n",
+    );
+    text.push_str(&learned);
+
+    assert_eq!(
+      mapping.learn(Action::Scale(2.0), &pad).unwrap(),
+      "bind pad scale 2\n"
+    );
+    assert_eq!(mapping.learn(Action::Scale(2.0), &pad).unwrap(), "");
+    text.push_str("bind pad scale 2\n");
+
+    let other = message("Keystation", 0, 36, true, 127);
+
+    assert_eq!(mapping.learn(Action::Pop, &other).unwrap(), "");
+
+    assert_eq!(Mapping::parse(path, &text).unwrap(), mapping);
+
+    assert_eq!(
+      mapping.actions(&knob),
+      [(Action::Db, Gesture::Turn(Parameter::from(0)))],
+    );
+
+    let button = Message {
+      channel: 0,
+      event: Event::ControlChange { value: 127.into() },
+      number: 20,
+      port: "Launchpad".into(),
+    };
+
+    assert_eq!(
+      mapping.learn(Action::Pop, &button).unwrap(),
+      "control cc-1-20 1 cc 20 Launchpad\nbind cc-1-20 pop\n",
+    );
+
+    assert_eq!(mapping.actions(&button), [(Action::Pop, Gesture::Press)]);
+
+    let program = Message {
+      channel: 0,
+      event: Event::ProgramChange,
+      number: 5,
+      port: "Launchpad".into(),
+    };
+
+    assert_eq!(
+      mapping.learn(Action::Db, &program).unwrap_err().to_string(),
+      "cannot learn continuous action `db` from a program change",
+    );
+  }
+
+  #[test]
+  fn kinds() {
+    let mut mapping = Mapping::parse(
+      Path::new("test.mapping"),
+      "control fader 1 cc 7
+control fine 1 cc14 8
+control bend 1 pitch-bend 0
+control pressure 1 aftertouch 0
+control patch 1 program 3
+bind fader db
+bind fine zoom
+bind bend horizontal
+bind pressure alpha
+bind patch pop
+",
+    )
+    .unwrap();
+
+    assert_eq!(mapping.wide("port"), [(0, 8)]);
+
+    let event = |channel, number, event| Message {
+      channel,
+      event,
+      number,
+      port: "port".into(),
+    };
+
+    assert_eq!(
+      mapping.actions(&event(0, 7, Event::ControlChange { value: 127.into() })),
+      [(Action::Db, Gesture::Turn(Parameter::from(63)))],
+    );
+
+    assert!(
+      mapping
+        .actions(&event(0, 8, Event::ControlChange { value: 127.into() }))
+        .is_empty()
+    );
+
+    assert_eq!(
+      mapping.actions(&event(
+        0,
+        8,
+        Event::ControlChange14 {
+          value: 16383.into()
+        }
+      )),
+      [(Action::Zoom, Gesture::Turn(Parameter::from(63)))],
+    );
+
+    assert_eq!(
+      mapping.actions(&event(0, 0, Event::PitchBend { value: 8192.into() })),
+      [(Action::Horizontal, Gesture::Turn(Parameter::from(0)))],
+    );
+
+    assert_eq!(
+      mapping.actions(&event(
+        0,
+        0,
+        Event::ChannelAftertouch { pressure: 0.into() }
+      )),
+      [(Action::Alpha, Gesture::Turn(Parameter::from(-64)))],
+    );
+
+    assert_eq!(
+      mapping.actions(&event(0, 3, Event::ProgramChange)),
+      [(Action::Pop, Gesture::Press)],
+    );
+  }
+
+  #[test]
+  fn port() {
+    let mut mapping = Mapping::parse(
+      Path::new("test.mapping"),
+      "control pad 1 note 36 Launchpad Mini # trailing comment\nbind pad pop\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+      mapping.actions(&message("Novation Launchpad Mini MK3", 0, 36, true, 127)),
+      [(Action::Pop, Gesture::Press)],
+    );
+
+    assert!(
+      mapping
+        .actions(&message("Midi Fighter Spectra", 0, 36, true, 127))
+        .is_empty()
+    );
+  }
+}
@@ -0,0 +1,19 @@
+use super::*;
+
+#[derive(Clone, Copy)]
+pub(crate) struct Measurement {
+  pub(crate) bands: [f32; Band::COUNT],
+  pub(crate) beat: f32,
+  pub(crate) channel_range: f32,
+  pub(crate) chroma: [f32; Chroma::PITCH_CLASSES],
+  pub(crate) correlation: f32,
+  pub(crate) frequency_range: f32,
+  pub(crate) gain: f32,
+  pub(crate) history: u32,
+  pub(crate) onset: f32,
+  pub(crate) pitch: Option<u32>,
+  pub(crate) rms: f32,
+  pub(crate) sample_range: f32,
+  pub(crate) spectrum_range: f32,
+  pub(crate) upcoming: f32,
+}
@@ -1,4 +1,4 @@
-use {super::*, midly::num::u7};
+use super::*;
 
 #[derive(Debug, Snafu)]
 #[snafu(context(suffix(Error)))]
@@ -11,88 +11,125 @@ pub(crate) enum MessageParseError {
   },
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub(crate) struct Message {
-  pub(crate) control: u8,
-  pub(crate) device: Device,
+  pub(crate) channel: u8,
   pub(crate) event: Event,
+  pub(crate) number: u8,
+  pub(crate) port: Arc<str>,
 }
 
 impl Message {
-  pub(crate) fn parse(event: &[u8]) -> Result<Self, MessageParseError> {
+  pub(crate) fn parse(
+    port: Arc<str>,
+    event: &[u8],
+    controllers: &mut Controllers,
+  ) -> Result<Self, MessageParseError> {
     let event = midly::live::LiveEvent::parse(event).context(ParseError)?;
-    let (channel, key, parameter, press): (u8, u8, u7, bool) = match event {
-      midly::live::LiveEvent::Midi { channel, message } => match message {
-        midly::MidiMessage::NoteOn { key, vel } => (channel.into(), key.into(), vel, true),
-        midly::MidiMessage::NoteOff { key, vel } => (channel.into(), key.into(), vel, false),
-        _ => {
-          return Err(MessageParseError::Unrecognized {
-            event: event.to_static(),
-          });
-        }
-      },
-      _ => {
-        return Err(MessageParseError::Unrecognized {
-          event: event.to_static(),
-        });
-      }
+
+    let midly::live::LiveEvent::Midi { channel, message } = event else {
+      return Err(MessageParseError::Unrecognized {
+        event: event.to_static(),
+      });
     };
 
-    let (device, control, event) = match (channel, key) {
-      (0, 0..=15) => (Device::Twister, key, Event::Encoder(parameter.into())),
-      (1, 0..=15) => (Device::Twister, key, Event::Button(press)),
-      (2, 36..=51) => (
-        Device::Spectra,
-        match key {
-          48 => 0,
-          49 => 1,
-          50 => 2,
-          51 => 3,
-          44 => 4,
-          45 => 5,
-          46 => 6,
-          47 => 7,
-          40 => 8,
-          41 => 9,
-          42 => 10,
-          43 => 11,
-          36 => 12,
-          37 => 13,
-          38 => 14,
-          39 => 15,
-          _ => unreachable!(),
+    let channel = u8::from(channel);
+
+    let (number, event) = match message {
+      midly::MidiMessage::Aftertouch { key, vel } => {
+        (key.into(), Event::PolyAftertouch { pressure: vel })
+      }
+      midly::MidiMessage::ChannelAftertouch { vel } => {
+        (0, Event::ChannelAftertouch { pressure: vel })
+      }
+      midly::MidiMessage::Controller { controller, value } => {
+        controllers.control_change(channel, controller.into(), value)
+      }
+      midly::MidiMessage::NoteOff { key, vel } => (
+        key.into(),
+        Event::Note {
+          press: false,
+          velocity: vel,
         },
-        Event::Button(press),
       ),
-      (3, 20..=25) => (
-        Device::Spectra,
-        match key {
-          22 => 16,
-          21 => 17,
-          20 => 18,
-          25 => 19,
-          24 => 20,
-          23 => 21,
-          _ => unreachable!(),
+      midly::MidiMessage::NoteOn { key, vel } => (
+        key.into(),
+        Event::Note {
+          press: true,
+          velocity: vel,
         },
-        Event::Button(press),
       ),
-      (3, 8..=13) => (Device::Twister, key - 8 + 16, Event::Button(press)),
-      _ => {
-        return Err(MessageParseError::Unrecognized {
-          event: event.to_static(),
-        });
-      }
+      midly::MidiMessage::PitchBend { bend } => (0, Event::PitchBend { value: bend.0 }),
+      midly::MidiMessage::ProgramChange { program } => (program.into(), Event::ProgramChange),
     };
 
     Ok(Self {
-      control,
-      device,
+      channel,
       event,
+      number,
+      port,
     })
   }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse() {
+    let mut controllers = Controllers::new([(1, 7)]);
+
+    let mut parse = |bytes: &[u8]| {
+      let message = Message::parse("port".into(), bytes, &mut controllers).unwrap();
+      (message.channel, message.number, message.event)
+    };
+
+    assert!(matches!(
+      parse(&[0xB1, 7, 100]),
+      (1, 7, Event::ControlChange14 { value }) if value == 100 << 7
+    ));
+
+    assert!(matches!(
+      parse(&[0xB1, 39, 5]),
+      (1, 7, Event::ControlChange14 { value }) if value == 100 << 7 | 5
+    ));
+
+    assert!(matches!(
+      parse(&[0xB2, 39, 5]),
+      (2, 39, Event::ControlChange { value }) if value == 5
+    ));
+
+    assert!(matches!(
+      parse(&[0xB1, 0, 127]),
+      (1, 0, Event::ControlChange { value }) if value == 127
+    ));
+
+    assert!(matches!(
+      parse(&[0xB1, 33, 127]),
+      (1, 33, Event::ControlChange { value }) if value == 127
+    ));
+
+    assert!(matches!(
+      parse(&[0xE0, 0x00, 0x40]),
+      (0, 0, Event::PitchBend { value }) if value == 8192
+    ));
+
+    assert!(matches!(
+      parse(&[0xD3, 90]),
+      (3, 0, Event::ChannelAftertouch { pressure }) if pressure == 90
+    ));
+
+    assert!(matches!(
+      parse(&[0xA0, 60, 30]),
+      (0, 60, Event::PolyAftertouch { pressure }) if pressure == 30
+    ));
+
+    assert!(matches!(parse(&[0xC4, 12]), (4, 12, Event::ProgramChange)));
 
-  pub(crate) fn tuple(self) -> (Device, u8, Event) {
-    (self.device, self.control, self.event)
+    assert!(matches!(
+      parse(&[0x90, 36, 127]),
+      (0, 36, Event::Note { press: true, velocity }) if velocity == 127
+    ));
   }
 }
@@ -0,0 +1,145 @@
+use super::*;
+
+pub(crate) struct Mix {
+  names: Vec<String>,
+  tracks: Vec<Track>,
+}
+
+impl Mix {
+  pub(crate) fn names(&self) -> &[String] {
+    &self.names
+  }
+
+  fn new(names: Vec<String>, tracks: Vec<Track>) -> Option<Self> {
+    let first = tracks.first()?;
+
+    let format = |track: &Track| (Source::channels(track), Source::sample_rate(track));
+
+    tracks
+      .iter()
+      .all(|track| format(track) == format(first))
+      .then_some(Self { names, tracks })
+  }
+
+  pub(crate) fn open(path: &Path) -> Result<Self> {
+    let mut paths = fs::read_dir(path)
+      .context(error::FilesystemIo { path })?
+      .map(|entry| Ok(entry?.path()))
+      .collect::<io::Result<Vec<PathBuf>>>()
+      .context(error::FilesystemIo { path })?
+      .into_iter()
+      .filter(|path| {
+        path
+          .extension()
+          .and_then(OsStr::to_str)
+          .is_some_and(|extension| Library::EXTENSIONS.contains(&extension.to_lowercase().as_str()))
+      })
+      .collect::<Vec<PathBuf>>();
+
+    paths.sort();
+
+    for (i, path) in paths.iter().enumerate() {
+      log::info!("source {}: {}", i + 1, path.display());
+    }
+
+    let tracks = paths
+      .iter()
+      .map(|path| Track::open(path, Duration::ZERO))
+      .collect::<Result<Vec<Track>>>()?;
+
+    let names = paths
+      .iter()
+      .map(|path| {
+        path
+          .file_stem()
+          .unwrap_or_default()
+          .to_string_lossy()
+          .into_owned()
+      })
+      .collect();
+
+    Self::new(names, tracks).context(error::StemFormat { path })
+  }
+
+  pub(crate) fn tracks(&self) -> &[Track] {
+    &self.tracks
+  }
+}
+
+impl Source for Mix {
+  fn channels(&self) -> u16 {
+    Source::channels(&self.tracks[0])
+  }
+
+  fn current_span_len(&self) -> Option<usize> {
+    None
+  }
+
+  fn sample_rate(&self) -> u32 {
+    Source::sample_rate(&self.tracks[0])
+  }
+
+  fn total_duration(&self) -> Option<Duration> {
+    self
+      .tracks
+      .iter()
+      .map(Source::total_duration)
+      .try_fold(Duration::ZERO, |max, duration| Some(max.max(duration?)))
+  }
+
+  fn try_seek(&mut self, position: Duration) -> Result<(), SeekError> {
+    for track in &mut self.tracks {
+      track.try_seek(position)?;
+    }
+    Ok(())
+  }
+}
+
+impl Iterator for Mix {
+  type Item = f32;
+
+  fn next(&mut self) -> Option<f32> {
+    let mut mixed = None;
+
+    for track in &mut self.tracks {
+      if let Some(sample) = track.next() {
+        *mixed.get_or_insert(0.0) += sample;
+      }
+    }
+
+    mixed
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, rodio::buffer::SamplesBuffer};
+
+  fn track(channels: u16, samples: &[f32]) -> Track {
+    Track::new(
+      SamplesBuffer::new(channels, 10, samples.to_vec()),
+      Duration::ZERO,
+    )
+  }
+
+  #[test]
+  fn mix() {
+    let drums = track(1, &[1.0, 2.0, 3.0]);
+    let bass = track(1, &[10.0, 20.0]);
+
+    let mut mix = Mix::new(
+      vec!["drums".into(), "bass".into()],
+      vec![drums.clone(), bass],
+    )
+    .unwrap();
+
+    assert_eq!(mix.by_ref().collect::<Vec<f32>>(), [11.0, 22.0, 3.0]);
+
+    let mut samples = Vec::new();
+    mix.tracks()[0].clone().drain(&mut samples);
+    assert_eq!(samples, [1.0, 2.0, 3.0]);
+
+    assert!(Mix::new(Vec::new(), vec![track(1, &[0.0]), track(2, &[0.0, 0.0])]).is_none());
+    assert!(Mix::new(Vec::new(), Vec::new()).is_none());
+  }
+}
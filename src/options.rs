@@ -8,7 +8,7 @@ use {
 
 #[derive(Clone, Default, Parser)]
 #[command(
-  group(ArgGroup::new("audio").args(["input", "song", "track"])),
+  group(ArgGroup::new("audio").args(["calibrate", "playlist", "signal", "song", "stems", "track"])),
   version,
   styles = Styles::styled()
     .error(AnsiColor::Red.on_default() | Effects::BOLD)
@@ -20,12 +20,64 @@ use {
     .valid(AnsiColor::Green.on_default())
 )]
 pub(crate) struct Options {
+  #[arg(long)]
+  pub(crate) auto_gain: bool,
+  #[arg(long, requires = "precompute")]
+  pub(crate) autopilot: bool,
+  #[arg(long)]
+  pub(crate) band_attack: Option<f32>,
+  #[arg(long)]
+  pub(crate) band_release: Option<f32>,
+  #[arg(long)]
+  pub(crate) buffer_size: Option<u32>,
+  #[arg(long)]
+  pub(crate) calibrate: bool,
+  #[arg(long, requires = "playlist")]
+  pub(crate) crossfade: Option<f32>,
   #[arg(long)]
   pub(crate) db: Option<f32>,
+  #[arg(
+    long,
+    value_parser = clap::value_parser!(u32).range(2..),
+  )]
+  pub(crate) fft_size: Option<u32>,
   #[arg(long)]
   pub(crate) fit: bool,
+  #[arg(
+    long,
+    value_parser = clap::value_parser!(u32).range(1..),
+  )]
+  pub(crate) hop: Option<u32>,
+  #[arg(long, requires = "status")]
+  pub(crate) hud: Vec<Placement>,
   #[arg(long)]
   pub(crate) input: bool,
+  #[arg(long, requires = "input")]
+  pub(crate) input_channels: Option<u16>,
+  #[arg(long)]
+  pub(crate) input_device: Option<String>,
+  #[arg(long, allow_negative_numbers = true)]
+  pub(crate) latency: Option<f32>,
+  #[arg(long)]
+  pub(crate) library: Vec<PathBuf>,
+  #[arg(long, allow_negative_numbers = true, requires = "auto_gain")]
+  pub(crate) loudness_target: Option<f32>,
+  #[arg(long)]
+  pub(crate) mapping: Option<PathBuf>,
+  #[arg(long)]
+  pub(crate) max_frequency: Option<f32>,
+  #[arg(long)]
+  pub(crate) min_frequency: Option<f32>,
+  #[arg(long)]
+  pub(crate) output_device: Option<String>,
+  #[arg(long)]
+  pub(crate) peak_decay: Option<f32>,
+  #[arg(long)]
+  pub(crate) peak_hold: Option<f32>,
+  #[arg(long)]
+  pub(crate) playlist: Option<PathBuf>,
+  #[arg(long)]
+  pub(crate) precompute: bool,
   #[arg(long)]
   pub(crate) program: Option<Program>,
   #[arg(long)]
@@ -38,22 +90,132 @@ pub(crate) struct Options {
   )]
   pub(crate) resolution: Option<u32>,
   #[arg(long)]
+  pub(crate) sample_rate: Option<u32>,
+  #[arg(long)]
+  pub(crate) seek_step: Option<f32>,
+  #[arg(long)]
+  pub(crate) signal: Option<Signal>,
+  #[arg(long, conflicts_with_all = ["output_device", "volume"])]
+  pub(crate) silent: bool,
+  #[arg(long)]
   pub(crate) song: Option<String>,
   #[arg(long)]
+  pub(crate) spectrum_attack: Option<f32>,
+  #[arg(
+    long,
+    value_parser = clap::value_parser!(u32).range(1..),
+  )]
+  pub(crate) spectrum_bins: Option<u32>,
+  #[arg(long)]
+  pub(crate) spectrum_db: bool,
+  #[arg(long, allow_negative_numbers = true, requires = "spectrum_db")]
+  pub(crate) spectrum_floor: Option<f32>,
+  #[arg(long)]
+  pub(crate) spectrum_release: Option<f32>,
+  #[arg(long)]
+  pub(crate) spectrum_scale: Option<Scale>,
+  #[arg(long)]
   pub(crate) status: bool,
   #[arg(long)]
+  pub(crate) stems: Option<PathBuf>,
+  #[arg(long)]
   pub(crate) tile: bool,
   #[arg(long)]
   pub(crate) track: Option<PathBuf>,
   #[arg(long)]
   pub(crate) volume: Option<f32>,
+  #[arg(long)]
+  pub(crate) window_function: Option<WindowFunction>,
 }
 
 impl Options {
+  pub(crate) fn max_frequency(&self) -> f32 {
+    self.max_frequency.unwrap_or(15_000.0)
+  }
+
+  pub(crate) fn min_frequency(&self) -> f32 {
+    self.min_frequency.unwrap_or(20.0)
+  }
+
   pub(crate) fn resolution(&self, window_size: PhysicalSize<u32>) -> u32 {
     self
       .resolution
       .unwrap_or(window_size.height.max(window_size.width))
       .max(1)
   }
+
+  pub(crate) fn spectrum_floor(&self) -> f32 {
+    self.spectrum_floor.unwrap_or(-60.0)
+  }
+
+  pub(crate) fn validate(&self) -> Result {
+    let max = self.max_frequency();
+    let min = self.min_frequency();
+
+    if !(min >= 0.0 && min < max && max.is_finite()) {
+      return Err(error::FrequencyRange { max, min }.build());
+    }
+
+    if let Some(Scale::Log) = self.spectrum_scale
+      && min <= 0.0
+    {
+      return Err(error::LogFrequency { min }.build());
+    }
+
+    let floor = self.spectrum_floor();
+
+    if !(floor < 0.0 && floor.is_finite()) {
+      return Err(error::SpectrumFloor { floor }.build());
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn validate() {
+    #[track_caller]
+    fn case(min: f32, max: f32, scale: Scale, floor: f32) -> Result {
+      Options {
+        max_frequency: Some(max),
+        min_frequency: Some(min),
+        spectrum_floor: Some(floor),
+        spectrum_scale: Some(scale),
+        ..default()
+      }
+      .validate()
+    }
+
+    assert!(Options::default().validate().is_ok());
+    assert!(case(0.0, 20_000.0, Scale::Linear, -60.0).is_ok());
+    assert!(case(20.0, 20_000.0, Scale::Log, -60.0).is_ok());
+
+    assert!(matches!(
+      case(0.0, 20_000.0, Scale::Log, -60.0),
+      Err(Error::LogFrequency { .. }),
+    ));
+
+    for (min, max) in [
+      (1000.0, 1000.0),
+      (2000.0, 1000.0),
+      (-1.0, 1000.0),
+      (f32::NAN, 1000.0),
+    ] {
+      assert!(matches!(
+        case(min, max, Scale::Linear, -60.0),
+        Err(Error::FrequencyRange { .. }),
+      ));
+    }
+
+    for floor in [0.0, 10.0, f32::NEG_INFINITY] {
+      assert!(matches!(
+        case(20.0, 20_000.0, Scale::Linear, floor),
+        Err(Error::SpectrumFloor { .. }),
+      ));
+    }
+  }
 }
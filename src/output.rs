@@ -0,0 +1,57 @@
+use super::*;
+
+pub(crate) enum Output {
+  Device(OutputStream),
+  Null {
+    mixer: Mixer,
+    running: Arc<AtomicBool>,
+  },
+}
+
+impl Output {
+  const CHANNELS: u16 = 2;
+  const PERIOD: Duration = Duration::from_millis(10);
+  const SAMPLE_RATE: u32 = 48_000;
+
+  pub(crate) fn mixer(&self) -> &Mixer {
+    match self {
+      Self::Device(stream) => stream.mixer(),
+      Self::Null { mixer, .. } => mixer,
+    }
+  }
+
+  pub(crate) fn null() -> Self {
+    let (mixer, mut source) = rodio::mixer::mixer(Self::CHANNELS, Self::SAMPLE_RATE);
+
+    let running = Arc::new(AtomicBool::new(true));
+
+    std::thread::spawn({
+      let running = running.clone();
+      move || {
+        let start = Instant::now();
+        let mut consumed = 0u128;
+        while running.load(atomic::Ordering::Relaxed) {
+          let due = start.elapsed().as_micros()
+            * u128::from(Self::SAMPLE_RATE)
+            * u128::from(Self::CHANNELS)
+            / 1_000_000;
+          while consumed < due {
+            source.next();
+            consumed += 1;
+          }
+          std::thread::sleep(Self::PERIOD);
+        }
+      }
+    });
+
+    Self::Null { mixer, running }
+  }
+}
+
+impl Drop for Output {
+  fn drop(&mut self) {
+    if let Self::Null { running, .. } = self {
+      running.store(false, atomic::Ordering::Relaxed);
+    }
+  }
+}
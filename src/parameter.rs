@@ -11,7 +11,7 @@ pub(crate) enum ParameterError {
   PositiveOverflow { value: i8 },
 }
 
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub(crate) struct Parameter(i8);
 
 impl From<i8> for Parameter {
@@ -50,6 +50,13 @@ impl From<midly::num::u7> for Parameter {
   }
 }
 
+impl From<midly::num::u14> for Parameter {
+  fn from(n: midly::num::u14) -> Self {
+    // parameters have 7-bit resolution, so the least significant bits are dropped
+    midly::num::u7::from(u8::try_from(u16::from(n) >> 7).unwrap()).into()
+  }
+}
+
 impl FromStr for Parameter {
   type Err = ParameterError;
 
@@ -0,0 +1,45 @@
+#[derive(Clone, Copy, Default)]
+pub(crate) struct Peak {
+  hold: f32,
+  value: f32,
+}
+
+impl Peak {
+  pub(crate) fn update(&mut self, level: f32, elapsed: f32, hold: f32, decay: f32) {
+    if level >= self.value {
+      self.hold = hold;
+      self.value = level;
+    } else if self.hold > 0.0 {
+      self.hold -= elapsed;
+    } else {
+      self.value = (self.value * (-elapsed / decay).exp()).max(level);
+    }
+  }
+
+  pub(crate) fn value(self) -> f32 {
+    self.value
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn hold_and_decay() {
+    let mut peak = Peak::default();
+
+    peak.update(1.0, 0.1, 0.2, 0.5);
+    assert_eq!(peak.value(), 1.0);
+
+    peak.update(0.0, 0.1, 0.2, 0.5);
+    peak.update(0.0, 0.1, 0.2, 0.5);
+    assert_eq!(peak.value(), 1.0);
+
+    peak.update(0.0, 0.5, 0.2, 0.5);
+    assert!((peak.value() - (-1.0f32).exp()).abs() < 1e-6);
+
+    peak.update(0.5, 0.1, 0.2, 0.5);
+    assert_eq!(peak.value(), 0.5);
+  }
+}
@@ -0,0 +1,77 @@
+use super::*;
+
+pub(crate) struct Playhead {
+  pub(crate) duration: Option<Duration>,
+  pub(crate) looping: bool,
+  pub(crate) paused: bool,
+  pub(crate) position: Duration,
+}
+
+impl Playhead {
+  pub(crate) fn remaining(&self) -> Option<String> {
+    Some(format!(
+      "-{}",
+      Timestamp(self.duration?.saturating_sub(self.position)),
+    ))
+  }
+}
+
+impl Display for Playhead {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{} ", if self.paused { "‖" } else { "▶" })?;
+
+    write!(f, "{}", Timestamp(self.position))?;
+
+    if let Some(duration) = self.duration {
+      write!(f, " / {}", Timestamp(duration))?;
+    }
+
+    if self.looping {
+      write!(f, " ⟲")?;
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn display() {
+    assert_eq!(
+      Playhead {
+        duration: Some(Duration::from_secs(225)),
+        looping: false,
+        paused: false,
+        position: Duration::from_millis(83_900),
+      }
+      .to_string(),
+      "▶ 1:23 / 3:45",
+    );
+
+    assert_eq!(
+      Playhead {
+        duration: Some(Duration::from_secs(225)),
+        looping: false,
+        paused: false,
+        position: Duration::from_millis(83_900),
+      }
+      .remaining()
+      .unwrap(),
+      "-2:21",
+    );
+
+    assert_eq!(
+      Playhead {
+        duration: None,
+        looping: true,
+        paused: true,
+        position: Duration::from_secs(5),
+      }
+      .to_string(),
+      "‖ 0:05 ⟲",
+    );
+  }
+}
@@ -0,0 +1,190 @@
+use super::*;
+
+#[derive(Clone)]
+pub(crate) struct Entry {
+  pub(crate) path: PathBuf,
+  pub(crate) program: Option<Program>,
+}
+
+pub(crate) struct Playlist {
+  entries: Vec<Entry>,
+  index: usize,
+}
+
+impl Playlist {
+  const PROGRAM: &str = "#X-PROGRAM:";
+
+  pub(crate) fn advance<T>(
+    &mut self,
+    mut offset: isize,
+    mut open: impl FnMut(&Path) -> Result<T>,
+  ) -> Option<(Entry, T)> {
+    let start = self.index;
+
+    loop {
+      let Some(entry) = self.skip(offset).cloned() else {
+        self.index = start;
+        return None;
+      };
+
+      match open(&entry.path) {
+        Ok(opened) => return Some((entry, opened)),
+        Err(err) => {
+          log::warn!("skipping {}: {err}", entry.path.display());
+          offset = offset.signum();
+        }
+      }
+    }
+  }
+
+  pub(crate) fn current(&self) -> &Entry {
+    &self.entries[self.index]
+  }
+
+  pub(crate) fn load(path: &Path, library: &Library) -> Result<Self> {
+    let text = fs::read_to_string(path).context(error::FilesystemIo { path })?;
+    Self::parse(path, &text, |song| library.find(song))
+  }
+
+  fn parse(path: &Path, text: &str, find_song: impl Fn(&str) -> Result<PathBuf>) -> Result<Self> {
+    let base = path.parent().unwrap_or(Path::new(""));
+
+    let mut entries = Vec::new();
+    let mut program = None;
+
+    for (i, line) in text.lines().enumerate() {
+      let line = line.trim();
+
+      if let Some(name) = line.strip_prefix(Self::PROGRAM) {
+        let name = name.trim();
+        program = Some(
+          Program::from_str(name, true)
+            .ok()
+            .context(error::PlaylistProgram {
+              line: i + 1,
+              path,
+              program: name,
+            })?,
+        );
+        continue;
+      }
+
+      if line.is_empty() || line.starts_with('#') {
+        continue;
+      }
+
+      let candidate = base.join(line);
+
+      entries.push(Entry {
+        path: if candidate.is_file() {
+          candidate
+        } else {
+          find_song(line)?
+        },
+        program: program.take(),
+      });
+    }
+
+    if entries.is_empty() {
+      return Err(error::PlaylistEmpty { path }.build());
+    }
+
+    Ok(Self { entries, index: 0 })
+  }
+
+  pub(crate) fn peek(&self, offset: isize) -> Option<&Entry> {
+    self.entries.get(self.index.checked_add_signed(offset)?)
+  }
+
+  pub(crate) fn skip(&mut self, offset: isize) -> Option<&Entry> {
+    let index = self.index.checked_add_signed(offset)?;
+
+    if index >= self.entries.len() {
+      return None;
+    }
+
+    self.index = index;
+
+    Some(&self.entries[index])
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn advance() {
+    let mut playlist = Playlist {
+      entries: ["a", "b", "c", "d"]
+        .into_iter()
+        .map(|path| Entry {
+          path: path.into(),
+          program: None,
+        })
+        .collect(),
+      index: 0,
+    };
+
+    let open = |path: &Path| {
+      if path == Path::new("b") || path == Path::new("d") {
+        Err(Error::internal("unreadable"))
+      } else {
+        Ok(path.to_owned())
+      }
+    };
+
+    let (entry, opened) = playlist.advance(1, open).unwrap();
+    assert_eq!(entry.path, Path::new("c"));
+    assert_eq!(opened, Path::new("c"));
+
+    assert!(playlist.advance(1, open).is_none());
+    assert_eq!(playlist.current().path, Path::new("c"));
+
+    let (entry, _) = playlist.advance(-1, open).unwrap();
+    assert_eq!(entry.path, Path::new("a"));
+  }
+
+  #[test]
+  fn parse() {
+    let tempdir = TempDir::new().unwrap();
+
+    fs::write(tempdir.path().join("a.flac"), "").unwrap();
+
+    let path = tempdir.path().join("set.m3u");
+
+    let mut playlist = Playlist::parse(
+      &path,
+      "#EXTM3U\n#X-PROGRAM: spectrogram\na.flac\n\n#EXTINF:123,B\nsong b\n",
+      |song| Ok(format!("/library/{song}.mp3").into()),
+    )
+    .unwrap();
+
+    assert_eq!(playlist.current().path, tempdir.path().join("a.flac"));
+    assert!(matches!(
+      playlist.current().program,
+      Some(Program::Spectrogram)
+    ));
+
+    assert_eq!(
+      playlist.peek(1).unwrap().path,
+      Path::new("/library/song b.mp3"),
+    );
+    assert!(playlist.peek(1).unwrap().program.is_none());
+    assert!(playlist.peek(-1).is_none());
+
+    assert!(playlist.skip(1).is_some());
+    assert!(playlist.skip(1).is_none());
+    assert_eq!(playlist.current().path, Path::new("/library/song b.mp3"));
+
+    assert!(matches!(
+      Playlist::parse(&path, "#X-PROGRAM:bogus\na.flac\n", |_| unreachable!()),
+      Err(Error::PlaylistProgram { line: 1, .. }),
+    ));
+
+    assert!(matches!(
+      Playlist::parse(&path, "#EXTM3U\n", |_| unreachable!()),
+      Err(Error::PlaylistEmpty { .. }),
+    ));
+  }
+}
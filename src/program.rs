@@ -1,18 +1,29 @@
 use super::*;
 
-#[derive(Clone, Copy, ValueEnum)]
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
 pub(crate) enum Program {
   All,
+  Bands,
+  Beat,
   Bottom,
+  Calibrate,
+  Chroma,
   Circle,
   Frequencies,
+  Goniometer,
   Hello,
   Highwaystar,
+  MidSide,
   Middle,
   None,
+  Peaks,
   RedX,
   Rip,
+  Spectrogram,
+  Stereo,
   Top,
+  Upcoming,
+  Waveform,
   X,
 }
 
@@ -20,9 +31,32 @@ impl Program {
   pub(crate) fn state(self) -> State {
     match self {
       Self::All => State::default().invert().all().push(),
+      Self::Bands => State::default()
+        .invert()
+        .circle()
+        .band(Band::Bass)
+        .push()
+        .x()
+        .band(Band::High)
+        .push(),
+      Self::Beat => State::default().invert().beat().push(),
       Self::Bottom => State::default().invert().bottom().push(),
+      Self::Calibrate => State::default().invert().flash().band(Band::Mid).push(),
+      Self::Chroma => State::default()
+        .invert()
+        .chroma()
+        .push()
+        .hue()
+        .circle()
+        .push(),
       Self::Circle => State::default().invert().circle().push(),
       Self::Frequencies => State::default().invert().frequencies().push(),
+      Self::Goniometer => State::default()
+        .invert()
+        .goniometer()
+        .push()
+        .correlation()
+        .push(),
       Self::Hello => State::default()
         .db(-40)
         .text(Some(Text {
@@ -35,11 +69,22 @@ impl Program {
         .frequencies()
         .push(),
       Self::Highwaystar => State::default().invert().circle().scale(2.0).times(8),
+      Self::MidSide => State::default().invert().mid_side().push(),
       Self::Middle => State::default().invert().top().push().bottom().push(),
       Self::None => State::default(),
+      Self::Peaks => State::default()
+        .invert()
+        .frequencies()
+        .push()
+        .peaks()
+        .push(),
       Self::RedX => State::default().invert_r().x().push(),
       Self::Rip => State::default().invert().top().push().samples().push(),
+      Self::Spectrogram => State::default().invert().spectrogram().push(),
+      Self::Stereo => State::default().invert().stereo().push(),
       Self::Top => State::default().invert().top().push(),
+      Self::Upcoming => State::default().invert().circle().push().upcoming().push(),
+      Self::Waveform => State::default().invert().waveform().push(),
       Self::X => State::default().invert().x().push(),
     }
   }
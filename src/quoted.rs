@@ -0,0 +1,36 @@
+use super::*;
+
+pub(crate) struct Quoted<'a>(pub(crate) &'a str);
+
+impl Display for Quoted<'_> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    f.write_char('"')?;
+
+    for c in self.0.chars() {
+      match c {
+        '"' => f.write_str("\\\"")?,
+        '\\' => f.write_str("\\\\")?,
+        '\n' => f.write_str("\\n")?,
+        '\r' => f.write_str("\\r")?,
+        '\t' => f.write_str("\\t")?,
+        c if c.is_control() => write!(f, "\\u{:04x}", u32::from(c))?,
+        c => f.write_char(c)?,
+      }
+    }
+
+    f.write_char('"')
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn escape() {
+    assert_eq!(
+      Quoted("a \"b\" \\ c\n\u{1}").to_string(),
+      r#""a \"b\" \\ c\n\u0001""#,
+    );
+  }
+}
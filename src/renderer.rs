@@ -13,31 +13,50 @@ pub struct Renderer {
   frame_times: VecDeque<Instant>,
   frequencies: Texture,
   frequency_view: TextureView,
+  history: Vec<u64>,
   layout_context: LayoutContext,
+  left: Texture,
+  left_view: TextureView,
+  mid: Texture,
+  mid_view: TextureView,
   overlay_renderer: vello::Renderer,
   overlay_scene: vello::Scene,
+  peak_view: TextureView,
+  peaks: Texture,
   queue: Queue,
   recorder: Option<Arc<Mutex<Recorder>>>,
   render_pipeline: RenderPipeline,
   resolution: u32,
+  right: Texture,
+  right_view: TextureView,
   sample_view: TextureView,
   sampler: Sampler,
   samples: Texture,
+  side: Texture,
+  side_view: TextureView,
   size: Vec2u,
+  spectrogram: Texture,
+  spectrogram_view: TextureView,
   surface: Surface<'static>,
   uniform_buffer: Buffer,
   uniform_buffer_size: u32,
   uniform_buffer_stride: u32,
+  waveform: Texture,
+  waveform_view: TextureView,
 }
 
 impl Renderer {
-  fn bind_group(
-    &self,
-    back: &TextureView,
-    frequencies: &TextureView,
-    front: &TextureView,
-    samples: &TextureView,
-  ) -> BindGroup {
+  pub(crate) fn auto_gain(options: &Options, analyzer: &Analyzer) -> f32 {
+    if !options.auto_gain {
+      return 0.0;
+    }
+
+    analyzer.loudness().short_term().map_or(0.0, |lufs| {
+      (options.loudness_target.unwrap_or(-18.0) - lufs).clamp(-24.0, 24.0)
+    })
+  }
+
+  fn bind_group(&self, back: &TextureView, front: &TextureView) -> BindGroup {
     let mut i = 0;
     let mut binding = || {
       let binding = i;
@@ -57,19 +76,43 @@ impl Renderer {
         },
         BindGroupEntry {
           binding: binding(),
-          resource: BindingResource::TextureView(frequencies),
+          resource: BindingResource::TextureView(&self.frequency_view),
         },
         BindGroupEntry {
           binding: binding(),
           resource: BindingResource::TextureView(front),
         },
+        BindGroupEntry {
+          binding: binding(),
+          resource: BindingResource::TextureView(&self.left_view),
+        },
+        BindGroupEntry {
+          binding: binding(),
+          resource: BindingResource::TextureView(&self.mid_view),
+        },
         BindGroupEntry {
           binding: binding(),
           resource: BindingResource::Sampler(&self.sampler),
         },
         BindGroupEntry {
           binding: binding(),
-          resource: BindingResource::TextureView(samples),
+          resource: BindingResource::TextureView(&self.peak_view),
+        },
+        BindGroupEntry {
+          binding: binding(),
+          resource: BindingResource::TextureView(&self.right_view),
+        },
+        BindGroupEntry {
+          binding: binding(),
+          resource: BindingResource::TextureView(&self.sample_view),
+        },
+        BindGroupEntry {
+          binding: binding(),
+          resource: BindingResource::TextureView(&self.side_view),
+        },
+        BindGroupEntry {
+          binding: binding(),
+          resource: BindingResource::TextureView(&self.spectrogram_view),
         },
         BindGroupEntry {
           binding: binding(),
@@ -79,6 +122,10 @@ impl Renderer {
             size: Some(u64::from(self.uniform_buffer_size).try_into().unwrap()),
           }),
         },
+        BindGroupEntry {
+          binding: binding(),
+          resource: BindingResource::TextureView(&self.waveform_view),
+        },
       ],
       label: label!(),
     })
@@ -115,7 +162,7 @@ impl Renderer {
           ty: BindingType::Texture {
             multisampled: false,
             sample_type: TextureSampleType::Float { filterable: false },
-            view_dimension: TextureViewDimension::D1,
+            view_dimension: TextureViewDimension::D2,
           },
           visibility: ShaderStages::FRAGMENT,
         },
@@ -129,6 +176,26 @@ impl Renderer {
           },
           visibility: ShaderStages::FRAGMENT,
         },
+        BindGroupLayoutEntry {
+          binding: binding(),
+          count: None,
+          ty: BindingType::Texture {
+            multisampled: false,
+            sample_type: TextureSampleType::Float { filterable: false },
+            view_dimension: TextureViewDimension::D2,
+          },
+          visibility: ShaderStages::FRAGMENT,
+        },
+        BindGroupLayoutEntry {
+          binding: binding(),
+          count: None,
+          ty: BindingType::Texture {
+            multisampled: false,
+            sample_type: TextureSampleType::Float { filterable: false },
+            view_dimension: TextureViewDimension::D2,
+          },
+          visibility: ShaderStages::FRAGMENT,
+        },
         BindGroupLayoutEntry {
           binding: binding(),
           count: None,
@@ -141,7 +208,47 @@ impl Renderer {
           ty: BindingType::Texture {
             multisampled: false,
             sample_type: TextureSampleType::Float { filterable: false },
-            view_dimension: TextureViewDimension::D1,
+            view_dimension: TextureViewDimension::D2,
+          },
+          visibility: ShaderStages::FRAGMENT,
+        },
+        BindGroupLayoutEntry {
+          binding: binding(),
+          count: None,
+          ty: BindingType::Texture {
+            multisampled: false,
+            sample_type: TextureSampleType::Float { filterable: false },
+            view_dimension: TextureViewDimension::D2,
+          },
+          visibility: ShaderStages::FRAGMENT,
+        },
+        BindGroupLayoutEntry {
+          binding: binding(),
+          count: None,
+          ty: BindingType::Texture {
+            multisampled: false,
+            sample_type: TextureSampleType::Float { filterable: false },
+            view_dimension: TextureViewDimension::D2,
+          },
+          visibility: ShaderStages::FRAGMENT,
+        },
+        BindGroupLayoutEntry {
+          binding: binding(),
+          count: None,
+          ty: BindingType::Texture {
+            multisampled: false,
+            sample_type: TextureSampleType::Float { filterable: false },
+            view_dimension: TextureViewDimension::D2,
+          },
+          visibility: ShaderStages::FRAGMENT,
+        },
+        BindGroupLayoutEntry {
+          binding: binding(),
+          count: None,
+          ty: BindingType::Texture {
+            multisampled: false,
+            sample_type: TextureSampleType::Float { filterable: false },
+            view_dimension: TextureViewDimension::D2,
           },
           visibility: ShaderStages::FRAGMENT,
         },
@@ -155,6 +262,16 @@ impl Renderer {
           },
           visibility: ShaderStages::FRAGMENT,
         },
+        BindGroupLayoutEntry {
+          binding: binding(),
+          count: None,
+          ty: BindingType::Texture {
+            multisampled: false,
+            sample_type: TextureSampleType::Float { filterable: false },
+            view_dimension: TextureViewDimension::D2,
+          },
+          visibility: ShaderStages::FRAGMENT,
+        },
       ],
       label: label!(),
     })
@@ -286,7 +403,7 @@ impl Renderer {
     pass.draw(0..3, 0..1);
   }
 
-  pub async fn new(options: &Options, window: Arc<Window>) -> Result<Self> {
+  pub async fn new(options: &Options, window: Arc<Window>, sources: u32) -> Result<Self> {
     let mut size = window.inner_size();
     size.width = size.width.max(1);
     size.height = size.height.max(1);
@@ -389,15 +506,17 @@ impl Renderer {
     });
 
     let samples = device.create_texture(&TextureDescriptor {
-      dimension: TextureDimension::D1,
+      dimension: TextureDimension::D2,
       format: TextureFormat::R32Float,
       label: label!(),
       mip_level_count: 1,
       sample_count: 1,
       size: Extent3d {
         depth_or_array_layers: 1,
-        height: 1,
-        width: limits.max_texture_dimension_1d,
+        height: sources,
+        width: limits
+          .max_texture_dimension_1d
+          .min(limits.max_texture_dimension_2d),
       },
       usage: TextureUsages::COPY_DST | TextureUsages::TEXTURE_BINDING,
       view_formats: &[TextureFormat::R32Float],
@@ -406,15 +525,17 @@ impl Renderer {
     let sample_view = samples.create_view(&TextureViewDescriptor::default());
 
     let frequencies = device.create_texture(&TextureDescriptor {
-      dimension: TextureDimension::D1,
+      dimension: TextureDimension::D2,
       format: TextureFormat::R32Float,
       label: label!(),
       mip_level_count: 1,
       sample_count: 1,
       size: Extent3d {
         depth_or_array_layers: 1,
-        height: 1,
-        width: limits.max_texture_dimension_1d,
+        height: sources,
+        width: limits
+          .max_texture_dimension_1d
+          .min(limits.max_texture_dimension_2d),
       },
       usage: TextureUsages::COPY_DST | TextureUsages::TEXTURE_BINDING,
       view_formats: &[TextureFormat::R32Float],
@@ -422,6 +543,77 @@ impl Renderer {
 
     let frequency_view = frequencies.create_view(&TextureViewDescriptor::default());
 
+    let peaks = device.create_texture(&TextureDescriptor {
+      dimension: TextureDimension::D2,
+      format: TextureFormat::R32Float,
+      label: label!(),
+      mip_level_count: 1,
+      sample_count: 1,
+      size: Extent3d {
+        depth_or_array_layers: 1,
+        height: sources,
+        width: limits
+          .max_texture_dimension_1d
+          .min(limits.max_texture_dimension_2d),
+      },
+      usage: TextureUsages::COPY_DST | TextureUsages::TEXTURE_BINDING,
+      view_formats: &[TextureFormat::R32Float],
+    });
+
+    let peak_view = peaks.create_view(&TextureViewDescriptor::default());
+
+    let channel = || {
+      let texture = device.create_texture(&TextureDescriptor {
+        dimension: TextureDimension::D2,
+        format: TextureFormat::R32Float,
+        label: label!(),
+        mip_level_count: 1,
+        sample_count: 1,
+        size: Extent3d {
+          depth_or_array_layers: 1,
+          height: 2 * sources,
+          width: limits
+            .max_texture_dimension_1d
+            .min(limits.max_texture_dimension_2d),
+        },
+        usage: TextureUsages::COPY_DST | TextureUsages::TEXTURE_BINDING,
+        view_formats: &[TextureFormat::R32Float],
+      });
+      let view = texture.create_view(&TextureViewDescriptor::default());
+      (texture, view)
+    };
+
+    let (left, left_view) = channel();
+    let (mid, mid_view) = channel();
+    let (right, right_view) = channel();
+    let (side, side_view) = channel();
+
+    let history = |width| {
+      let texture = device.create_texture(&TextureDescriptor {
+        dimension: TextureDimension::D2,
+        format: TextureFormat::R32Float,
+        label: label!(),
+        mip_level_count: 1,
+        sample_count: 1,
+        size: Extent3d {
+          depth_or_array_layers: 1,
+          height: History::ROWS * sources,
+          width,
+        },
+        usage: TextureUsages::COPY_DST | TextureUsages::TEXTURE_BINDING,
+        view_formats: &[TextureFormat::R32Float],
+      });
+      let view = texture.create_view(&TextureViewDescriptor::default());
+      (texture, view)
+    };
+
+    let (spectrogram, spectrogram_view) = history(
+      limits
+        .max_texture_dimension_1d
+        .min(limits.max_texture_dimension_2d),
+    );
+    let (waveform, waveform_view) = history(2);
+
     let resolution = options.resolution(size);
 
     let overlay_renderer = vello::Renderer::new(
@@ -454,21 +646,36 @@ impl Renderer {
       frame_times: VecDeque::with_capacity(100),
       frequencies,
       frequency_view,
+      history: vec![0; sources.into_usize()],
       layout_context: LayoutContext::new(),
+      left,
+      left_view,
+      mid,
+      mid_view,
       overlay_renderer,
       overlay_scene: vello::Scene::new(),
+      peak_view,
+      peaks,
       queue,
       recorder,
       render_pipeline,
       resolution,
+      right,
+      right_view,
       sample_view,
       sampler,
       samples,
+      side,
+      side_view,
       size: Vec2u::new(size.width, size.height),
+      spectrogram,
+      spectrogram_view,
       surface,
       uniform_buffer,
       uniform_buffer_size,
       uniform_buffer_stride,
+      waveform,
+      waveform_view,
     };
 
     renderer.resize(options, size);
@@ -476,7 +683,15 @@ impl Renderer {
     Ok(renderer)
   }
 
-  pub(crate) fn render(&mut self, options: &Options, analyzer: &Analyzer, state: &State) -> Result {
+  pub(crate) fn render(
+    &mut self,
+    options: &Options,
+    analyzers: &[&Analyzer],
+    input: Option<&Analyzer>,
+    state: &State,
+    playhead: Option<Playhead>,
+    title: Option<&str>,
+  ) -> Result {
     match self.error_channel.try_recv() {
       Ok(error) => return Err(error::Validation.into_error(error)),
       Err(mpsc::TryRecvError::Empty) => {}
@@ -512,94 +727,130 @@ impl Renderer {
       size: tiling_size,
     };
 
-    let sample_count = analyzer
-      .samples()
-      .len()
-      .min(self.samples.width().into_usize());
-    let samples = &analyzer.samples()[..sample_count];
-    let sample_range = sample_count as f32 / self.samples.width() as f32;
-    self.write_texture(samples, &self.samples);
+    let mut measurements = Vec::new();
 
-    let frequency_count = analyzer
-      .frequencies()
-      .len()
-      .min(self.frequencies.width().into_usize());
-    let frequencies = &analyzer.frequencies()[..frequency_count];
-    let frequency_range = frequency_count as f32 / self.frequencies.width() as f32;
-    self.write_texture(frequencies, &self.frequencies);
+    for (source, analyzer) in analyzers.iter().take(self.history.len()).enumerate() {
+      measurements.push(self.write_analyzer(
+        options,
+        analyzer,
+        state.db,
+        u32::try_from(source).unwrap(),
+      ));
+    }
 
     let filter_count = u32::try_from(state.filters.len()).unwrap();
 
-    let gain = 10f32.powf(state.db / 20.0);
-
-    let rms = analyzer.rms();
-
     for (i, filter) in state.filters.iter().enumerate() {
       let i = u32::try_from(i).unwrap();
+      let source = filter
+        .source
+        .min(u32::try_from(measurements.len()).unwrap() - 1);
+      let measurement = measurements[source.into_usize()];
       uniforms.push(Uniforms {
         back_read: false,
+        band: filter.band,
+        bands: measurement.bands,
+        beat: measurement.beat,
+        channel_range: measurement.channel_range,
+        chroma: measurement.chroma,
         color: filter.color,
         coordinates: filter.coordinates,
+        correlation: measurement.correlation,
         field: filter.field,
         filters: filter_count,
         fit: false,
-        frequency_range,
+        frequency_range: measurement.frequency_range,
         front_offset: tiling.source_offset(i),
         front_read: true,
-        gain,
+        gain: measurement.gain,
+        history: measurement.history,
+        hue: filter.hue,
         index: i,
         offset: tiling.offset(i),
+        onset: measurement.onset,
+        pitch: measurement.pitch,
         position: filter.position,
         repeat: false,
         resolution: tiling.resolution(),
-        rms,
-        sample_range,
+        rms: measurement.rms,
+        sample_range: measurement.sample_range,
+        source,
+        spectrum_range: measurement.spectrum_range,
         tiling: tiling.size,
+        upcoming: measurement.upcoming,
         wrap: filter.wrap,
       });
     }
 
+    let measurement = measurements[0];
+
     uniforms.push(Uniforms {
       back_read: tiling.back_read(filter_count),
+      band: None,
+      bands: measurement.bands,
+      beat: measurement.beat,
+      channel_range: measurement.channel_range,
+      chroma: measurement.chroma,
       color: Mat4f::identity(),
       coordinates: false,
+      correlation: measurement.correlation,
       field: Field::None,
       filters: filter_count,
       fit: options.fit,
-      frequency_range,
+      frequency_range: measurement.frequency_range,
       front_offset: Vec2f::new(0.0, 0.0),
       front_read: tiling.front_read(filter_count),
-      gain,
+      gain: measurement.gain,
+      history: measurement.history,
+      hue: false,
       index: filter_count,
       offset: Vec2f::default(),
+      onset: measurement.onset,
+      pitch: measurement.pitch,
       position: Mat3f::identity(),
       repeat: options.repeat,
       resolution: Vec2f::new(self.resolution as f32, self.resolution as f32),
-      rms,
-      sample_range,
+      rms: measurement.rms,
+      sample_range: measurement.sample_range,
+      source: 0,
+      spectrum_range: measurement.spectrum_range,
       tiling: 1,
+      upcoming: measurement.upcoming,
       wrap: false,
     });
 
     uniforms.push(Uniforms {
       back_read: true,
+      band: None,
+      bands: measurement.bands,
+      beat: measurement.beat,
+      channel_range: measurement.channel_range,
+      chroma: measurement.chroma,
       color: Mat4f::identity(),
       coordinates: false,
+      correlation: measurement.correlation,
       field: Field::None,
       filters: filter_count,
       fit: options.fit,
-      frequency_range,
+      frequency_range: measurement.frequency_range,
       front_offset: Vec2f::new(0.0, 0.0),
       front_read: true,
-      gain,
+      gain: measurement.gain,
+      history: measurement.history,
+      hue: false,
       index: filter_count,
       offset: Vec2f::default(),
+      onset: measurement.onset,
+      pitch: measurement.pitch,
       position: Mat3f::identity(),
       repeat: options.repeat,
       resolution: Vec2f::new(self.size.x as f32, self.size.y as f32),
-      rms,
-      sample_range,
+      rms: measurement.rms,
+      sample_range: measurement.sample_range,
+      source: 0,
+      spectrum_range: measurement.spectrum_range,
       tiling: 1,
+      upcoming: measurement.upcoming,
       wrap: false,
     });
 
@@ -649,8 +900,8 @@ impl Renderer {
       &self.bindings().tiling_view,
     );
 
-    if options.status || state.text.is_some() {
-      self.render_overlay(options, state, fps)?;
+    if options.status || options.calibrate || state.text.is_some() {
+      self.render_overlay(options, analyzers[0], input, state, fps, playhead, title)?;
 
       self.draw(
         &self.bindings().overlay_bind_group,
@@ -705,8 +956,12 @@ impl Renderer {
   pub(crate) fn render_overlay(
     &mut self,
     options: &Options,
+    analyzer: &Analyzer,
+    input: Option<&Analyzer>,
     state: &State,
     fps: Option<f32>,
+    playhead: Option<Playhead>,
+    title: Option<&str>,
   ) -> Result {
     use {
       kurbo::{Affine, Rect, Vec2},
@@ -720,32 +975,43 @@ impl Renderer {
 
     self.overlay_scene.reset();
 
-    let text = if let Some(text) = state.text.clone() {
-      text
+    let texts = if let Some(text) = state.text.clone() {
+      vec![(text, Region::BottomLeft)]
     } else {
-      let mut items = Vec::new();
-
-      if let Some(fps) = fps {
-        items.push(format!("ƒ {}", fps.floor()));
-      }
-
-      let parameter = state.parameter.value();
-      items.push(if parameter >= 0 {
-        format!("+{parameter}")
+      let hud = Hud {
+        analyzer,
+        fps,
+        input,
+        options,
+        playhead: playhead.as_ref(),
+        state,
+        title,
+      };
+
+      let defaults;
+
+      let placements = if options.hud.is_empty() {
+        defaults = Placement::defaults();
+        &defaults
       } else {
-        parameter.to_string()
-      });
-
-      for filter in &state.filters {
-        items.push(filter.icon().into());
-      }
+        &options.hud
+      };
 
-      Text {
-        size: 0.033,
-        string: items.join(" "),
-        x: 0.0,
-        y: 0.0,
-      }
+      placements
+        .iter()
+        .map(|placement| {
+          (
+            Text {
+              size: 0.033,
+              string: hud.text(&placement.elements),
+              x: 0.0,
+              y: 0.0,
+            },
+            placement.region,
+          )
+        })
+        .filter(|(text, _)| !text.string.is_empty())
+        .collect()
     };
 
     let bounds = if options.fit {
@@ -778,68 +1044,81 @@ impl Renderer {
       }
     };
 
-    #[allow(clippy::cast_possible_truncation)]
-    let font_size = bounds.height() as f32 * text.size;
-
-    let mut builder =
-      self
-        .layout_context
-        .ranged_builder(&mut self.font_context, &text.string, 1.0, true);
-    builder.push_default(StyleProperty::FontSize(font_size));
-    builder.push_default(StyleProperty::FontStack(FontStack::List(Cow::Borrowed(&[
-      FontFamily::Named(FONT.into()),
-      FontFamily::Generic(GenericFamily::SansSerif),
-    ]))));
-    builder.push_default(StyleProperty::FontWeight(FontWeight::LIGHT));
-
-    let mut layout = builder.build(&text.string);
-    layout.break_all_lines(None);
-    layout.align(None, Alignment::Start, AlignmentOptions::default());
-
-    for line in layout.lines() {
-      for item in line.items() {
-        match item {
-          PositionedLayoutItem::GlyphRun(glyph_run) => {
-            let run = glyph_run.run();
-            let mut offset = glyph_run.offset();
-
-            self
-              .overlay_scene
-              .draw_glyphs(run.font())
-              .brush(&Brush::Solid(Color::WHITE))
-              .font_size(font_size)
-              .glyph_transform(
-                run
-                  .synthesis()
-                  .skew()
-                  .map(|angle| Affine::skew(angle.to_radians().tan().into(), 0.0)),
-              )
-              .hint(true)
-              .normalized_coords(run.normalized_coords())
-              .transform(Affine::translate(Vec2 {
-                x: text.x * bounds.width() + bounds.x0 + 10.0,
-                y: text.y * bounds.height() + bounds.y1
-                  - 10.0
-                  - f64::from(glyph_run.baseline())
-                  - f64::from(run.metrics().descent),
-              }))
-              .draw(
-                Fill::NonZero,
-                glyph_run.glyphs().map(|glyph| {
-                  let x = offset + glyph.x;
-                  offset += glyph.advance;
-                  vello::Glyph {
-                    id: glyph.id,
-                    x,
-                    y: glyph.y,
-                  }
-                }),
-              );
-          }
-          PositionedLayoutItem::InlineBox(_) => {
-            return Err(Error::internal(
-              "unexpected inline box while rendering overlay",
-            ));
+    for (text, region) in &texts {
+      #[allow(clippy::cast_possible_truncation)]
+      let font_size = bounds.height() as f32 * text.size;
+
+      let mut builder =
+        self
+          .layout_context
+          .ranged_builder(&mut self.font_context, &text.string, 1.0, true);
+      builder.push_default(StyleProperty::FontSize(font_size));
+      builder.push_default(StyleProperty::FontStack(FontStack::List(Cow::Borrowed(&[
+        FontFamily::Named(FONT.into()),
+        FontFamily::Generic(GenericFamily::SansSerif),
+      ]))));
+      builder.push_default(StyleProperty::FontWeight(FontWeight::LIGHT));
+
+      let mut layout = builder.build(&text.string);
+      layout.break_all_lines(None);
+      layout.align(None, Alignment::Start, AlignmentOptions::default());
+
+      let x = if region.right() {
+        bounds.x1 - 10.0 - f64::from(layout.width())
+      } else {
+        bounds.x0 + 10.0
+      };
+
+      for line in layout.lines() {
+        for item in line.items() {
+          match item {
+            PositionedLayoutItem::GlyphRun(glyph_run) => {
+              let run = glyph_run.run();
+              let mut offset = glyph_run.offset();
+
+              self
+                .overlay_scene
+                .draw_glyphs(run.font())
+                .brush(&Brush::Solid(Color::WHITE))
+                .font_size(font_size)
+                .glyph_transform(
+                  run
+                    .synthesis()
+                    .skew()
+                    .map(|angle| Affine::skew(angle.to_radians().tan().into(), 0.0)),
+                )
+                .hint(true)
+                .normalized_coords(run.normalized_coords())
+                .transform(Affine::translate(Vec2 {
+                  x: text.x * bounds.width() + x,
+                  y: text.y * bounds.height()
+                    + if region.bottom() {
+                      bounds.y1
+                        - 10.0
+                        - f64::from(glyph_run.baseline())
+                        - f64::from(run.metrics().descent)
+                    } else {
+                      bounds.y0 + 10.0 + f64::from(glyph_run.baseline())
+                    },
+                }))
+                .draw(
+                  Fill::NonZero,
+                  glyph_run.glyphs().map(|glyph| {
+                    let x = offset + glyph.x;
+                    offset += glyph.advance;
+                    vello::Glyph {
+                      id: glyph.id,
+                      x,
+                      y: glyph.y,
+                    }
+                  }),
+                );
+            }
+            PositionedLayoutItem::InlineBox(_) => {
+              return Err(Error::internal(
+                "unexpected inline box while rendering overlay",
+              ));
+            }
           }
         }
       }
@@ -892,12 +1171,7 @@ impl Renderer {
 
     let targets = [self.target(&tiling_view), self.target(&tiling_view)];
 
-    let tiling_bind_group = self.bind_group(
-      &targets[0].texture_view,
-      &self.frequency_view,
-      &targets[1].texture_view,
-      &self.sample_view,
-    );
+    let tiling_bind_group = self.bind_group(&targets[0].texture_view, &targets[1].texture_view);
 
     let overlay_view = self
       .device
@@ -917,12 +1191,7 @@ impl Renderer {
       })
       .create_view(&TextureViewDescriptor::default());
 
-    let overlay_bind_group = self.bind_group(
-      &tiling_view,
-      &self.frequency_view,
-      &overlay_view,
-      &self.sample_view,
-    );
+    let overlay_bind_group = self.bind_group(&tiling_view, &overlay_view);
 
     self.bindings = Some(Bindings {
       captures: Arc::new(Mutex::new(Vec::new())),
@@ -961,7 +1230,7 @@ impl Renderer {
 
     let texture_view = texture.create_view(&TextureViewDescriptor::default());
 
-    let bind_group = self.bind_group(back, &self.frequency_view, &texture_view, &self.sample_view);
+    let bind_group = self.bind_group(back, &texture_view);
 
     Target {
       bind_group,
@@ -970,12 +1239,97 @@ impl Renderer {
     }
   }
 
-  fn write_texture(&self, data: &[f32], destination: &Texture) {
+  fn write_analyzer(
+    &mut self,
+    options: &Options,
+    analyzer: &Analyzer,
+    db: f32,
+    source: u32,
+  ) -> Measurement {
+    let sample_count = analyzer
+      .samples()
+      .len()
+      .min(self.samples.width().into_usize());
+    let samples = &analyzer.samples()[..sample_count];
+    let sample_range = sample_count as f32 / self.samples.width() as f32;
+    self.write_texture(samples, &self.samples, source);
+
+    let frequency_count = analyzer
+      .frequencies()
+      .len()
+      .min(self.frequencies.width().into_usize());
+    let frequencies = &analyzer.frequencies()[..frequency_count];
+    let frequency_range = frequency_count as f32 / self.frequencies.width() as f32;
+    self.write_texture(frequencies, &self.frequencies, source);
+
+    let peaks = &analyzer.peaks()[..analyzer.peaks().len().min(frequency_count)];
+    self.write_texture(peaks, &self.peaks, source);
+
+    let width = self.left.width().into_usize();
+    let channel_range = analyzer.mono().samples().len().min(width) as f32 / width as f32;
+    let spectrum_count = analyzer.mono().spectrum().len().min(width);
+    let spectrum_range = spectrum_count as f32 / width as f32;
+
+    for (channel, texture) in [
+      (analyzer.left(), &self.left),
+      (analyzer.mid(), &self.mid),
+      (analyzer.right(), &self.right),
+      (analyzer.side(), &self.side),
+    ] {
+      let samples = channel.samples();
+      self.write_texture(&samples[..samples.len().min(width)], texture, source * 2);
+      let spectrum = channel.spectrum();
+      self.write_texture(
+        &spectrum[..spectrum.len().min(width)],
+        texture,
+        source * 2 + 1,
+      );
+    }
+
+    let since = self.history[source.into_usize()];
+
+    for (history, texture) in [
+      (analyzer.spectrogram(), &self.spectrogram),
+      (analyzer.waveform(), &self.waveform),
+    ] {
+      let width = texture.width().into_usize();
+      for (frame, row) in history.since(since) {
+        self.write_texture(
+          &row[..row.len().min(width)],
+          texture,
+          source * History::ROWS + u32::try_from(frame % u64::from(History::ROWS)).unwrap(),
+        );
+      }
+    }
+
+    let frame = analyzer.spectrogram().frame();
+
+    self.history[source.into_usize()] = frame;
+
+    Measurement {
+      bands: analyzer.bands(),
+      beat: analyzer.beat(),
+      channel_range,
+      chroma: analyzer.chroma(),
+      correlation: analyzer.correlation(),
+      frequency_range,
+      gain: 10f32.powf((db + Self::auto_gain(options, analyzer)) / 20.0),
+      history: u32::try_from(frame.saturating_sub(1) % u64::from(History::ROWS)).unwrap(),
+      onset: analyzer.onset(),
+      pitch: analyzer.pitch(),
+      rms: analyzer.rms(),
+      sample_range,
+      spectrum_range,
+      upcoming: analyzer.upcoming(),
+    }
+  }
+
+  fn write_texture(&self, data: &[f32], destination: &Texture, row: u32) {
     self.queue.write_texture(
       TexelCopyTextureInfo {
         texture: destination,
         mip_level: 0,
-        origin: Origin3d::ZERO,
+        origin: Origin3d { x: 0, y: row, z: 0 },
         aspect: TextureAspect::All,
       },
       &data
@@ -0,0 +1,35 @@
+use super::*;
+
+pub(crate) struct Samples {
+  channels: u16,
+  sample_rate: u32,
+  samples: Vec<f32>,
+}
+
+impl Samples {
+  pub(crate) fn new(channels: u16, sample_rate: u32, samples: Vec<f32>) -> Self {
+    Self {
+      channels,
+      sample_rate,
+      samples,
+    }
+  }
+}
+
+impl Stream for Samples {
+  fn channels(&self) -> u16 {
+    self.channels
+  }
+
+  fn done(&self) -> bool {
+    false
+  }
+
+  fn drain(&mut self, samples: &mut Vec<f32>) {
+    samples.append(&mut self.samples);
+  }
+
+  fn sample_rate(&self) -> u32 {
+    self.sample_rate
+  }
+}
@@ -0,0 +1,30 @@
+use super::*;
+
+#[derive(Clone, Copy, Default, ValueEnum)]
+pub(crate) enum Scale {
+  Bark,
+  #[default]
+  Linear,
+  Log,
+  Mel,
+}
+
+impl Scale {
+  pub(crate) fn to_hz(self, value: f32) -> f32 {
+    match self {
+      Self::Bark => 1960.0 * (value + 0.53) / (26.28 - value),
+      Self::Linear => value,
+      Self::Log => value.exp2(),
+      Self::Mel => 700.0 * (10f32.powf(value / 2595.0) - 1.0),
+    }
+  }
+
+  pub(crate) fn to_scale(self, hz: f32) -> f32 {
+    match self {
+      Self::Bark => 26.81 * hz / (1960.0 + hz) - 0.53,
+      Self::Linear => hz,
+      Self::Log => hz.log2(),
+      Self::Mel => 2595.0 * (1.0 + hz / 700.0).log10(),
+    }
+  }
+}
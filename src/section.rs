@@ -0,0 +1,17 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug, IntoStaticStr, PartialEq)]
+#[strum(serialize_all = "kebab-case")]
+pub(crate) enum Section {
+  Chorus,
+  Drop,
+  Intro,
+  Outro,
+  Verse,
+}
+
+impl Section {
+  pub(crate) fn name(self) -> &'static str {
+    self.into()
+  }
+}
@@ -19,6 +19,17 @@ pub(crate) trait Shared {
   fn write_aligned(&self, buffer: &mut [u8]);
 }
 
+impl<const N: usize> Shared for [f32; N] {
+  const ALIGNMENT: usize = 16;
+  const SIZE: usize = N.div_ceil(4) * 16;
+
+  fn write_aligned(&self, buffer: &mut [u8]) {
+    for (scalar, buffer) in self.iter().zip(buffer.chunks_mut(f32::SIZE)) {
+      scalar.write_aligned(buffer);
+    }
+  }
+}
+
 impl Shared for bool {
   const ALIGNMENT: usize = u32::ALIGNMENT;
   const SIZE: usize = u32::ALIGNMENT;
@@ -55,6 +66,26 @@ impl Shared for Field {
   }
 }
 
+impl Shared for Option<Band> {
+  const ALIGNMENT: usize = u32::ALIGNMENT;
+  const SIZE: usize = u32::SIZE;
+
+  fn write_aligned(&self, buffer: &mut [u8]) {
+    self
+      .map_or(u32::MAX, |band| band as u32)
+      .write_aligned(buffer);
+  }
+}
+
+impl Shared for Option<u32> {
+  const ALIGNMENT: usize = u32::ALIGNMENT;
+  const SIZE: usize = u32::SIZE;
+
+  fn write_aligned(&self, buffer: &mut [u8]) {
+    self.unwrap_or(u32::MAX).write_aligned(buffer);
+  }
+}
+
 impl Shared for Mat3f {
   const ALIGNMENT: usize = 16;
   const SIZE: usize = 48;
@@ -0,0 +1,270 @@
+use super::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(context(suffix(Error)))]
+pub(crate) enum SignalError {
+  #[snafu(display("signal `{name}` does not take an argument"))]
+  Argument { name: String },
+  #[snafu(transparent)]
+  Parse { source: num::ParseFloatError },
+  #[snafu(display("invalid sweep `{argument}`, expected `FROM-TO`"))]
+  Sweep { argument: String },
+  #[snafu(display(
+    "unknown signal `{name}`, expected one of `click`, `impulse`, `pink`, `sine`, `sweep` or `white`"
+  ))]
+  Unknown { name: String },
+  #[snafu(display("invalid signal value `{value}`, expected a positive number"))]
+  Value { value: f32 },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Signal {
+  Click { bpm: f32 },
+  Impulse { frequency: f32 },
+  Pink,
+  Sine { frequency: f32 },
+  Sweep { from: f32, to: f32 },
+  White,
+}
+
+impl FromStr for Signal {
+  type Err = SignalError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let (name, argument) = match s.split_once(':') {
+      Some((name, argument)) => (name, Some(argument)),
+      None => (s, None),
+    };
+
+    let positive = |value: &str| {
+      let value = value.parse::<f32>()?;
+      if value > 0.0 && value.is_finite() {
+        Ok(value)
+      } else {
+        Err(ValueError { value }.build())
+      }
+    };
+
+    let number = |default: f32| argument.map_or(Ok(default), positive);
+
+    match name {
+      "click" => Ok(Self::Click {
+        bpm: number(120.0)?,
+      }),
+      "impulse" => Ok(Self::Impulse {
+        frequency: number(1.0)?,
+      }),
+      "pink" | "white" => {
+        if argument.is_some() {
+          return Err(ArgumentError { name }.build());
+        }
+        Ok(if name == "pink" {
+          Self::Pink
+        } else {
+          Self::White
+        })
+      }
+      "sine" => Ok(Self::Sine {
+        frequency: number(440.0)?,
+      }),
+      "sweep" => {
+        let Some(argument) = argument else {
+          return Ok(Self::Sweep {
+            from: 20.0,
+            to: 20_000.0,
+          });
+        };
+
+        let (from, to) = argument.split_once('-').context(SweepError { argument })?;
+
+        Ok(Self::Sweep {
+          from: positive(from)?,
+          to: positive(to)?,
+        })
+      }
+      _ => Err(UnknownError { name }.build()),
+    }
+  }
+}
+
+pub(crate) struct Generator {
+  phase: f32,
+  pink: [f32; 3],
+  rng: u32,
+  sample: u64,
+  signal: Signal,
+  start: Option<Instant>,
+}
+
+impl Generator {
+  const SAMPLE_RATE: u32 = 48_000;
+  const SWEEP: f32 = 10.0;
+
+  pub(crate) fn new(signal: Signal) -> Self {
+    Self {
+      phase: 0.0,
+      pink: [0.0; 3],
+      rng: 0x9e37_79b9,
+      sample: 0,
+      signal,
+      start: None,
+    }
+  }
+
+  fn oscillate(&mut self, frequency: f32) -> f32 {
+    let sample = (TAU * self.phase).sin();
+    self.phase = (self.phase + frequency / Self::SAMPLE_RATE as f32).fract();
+    sample
+  }
+
+  fn white(&mut self) -> f32 {
+    self.rng ^= self.rng << 13;
+    self.rng ^= self.rng >> 17;
+    self.rng ^= self.rng << 5;
+    self.rng as f32 / u32::MAX as f32 * 2.0 - 1.0
+  }
+}
+
+impl Iterator for Generator {
+  type Item = f32;
+
+  #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+  fn next(&mut self) -> Option<f32> {
+    let rate = f64::from(Self::SAMPLE_RATE);
+    let t = self.sample as f64 / rate;
+
+    let sample = match self.signal {
+      Signal::Click { bpm } => Click::burst((t % (60.0 / f64::from(bpm))) as f32),
+      Signal::Impulse { frequency } => {
+        let period = (rate / f64::from(frequency)).round().max(1.0) as u64;
+        if self.sample.is_multiple_of(period) {
+          1.0
+        } else {
+          0.0
+        }
+      }
+      Signal::Pink => {
+        let white = self.white();
+        let [b0, b1, b2] = &mut self.pink;
+        *b0 = 0.997_65 * *b0 + 0.099_046 * white;
+        *b1 = 0.963 * *b1 + 0.296_516_4 * white;
+        *b2 = 0.57 * *b2 + 1.052_691_3 * white;
+        ((*b0 + *b1 + *b2 + 0.1848 * white) * 0.1).clamp(-1.0, 1.0)
+      }
+      Signal::Sine { frequency } => self.oscillate(frequency),
+      Signal::Sweep { from, to } => {
+        let position = (t as f32 % Self::SWEEP) / Self::SWEEP;
+        self.oscillate(from * (to / from).powf(position))
+      }
+      Signal::White => self.white(),
+    };
+
+    self.sample += 1;
+
+    Some(sample)
+  }
+}
+
+impl Stream for Generator {
+  fn channels(&self) -> u16 {
+    1
+  }
+
+  fn done(&self) -> bool {
+    false
+  }
+
+  #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+  fn drain(&mut self, samples: &mut Vec<f32>) {
+    let start = *self.start.get_or_insert_with(Instant::now);
+
+    let due = (start.elapsed().as_secs_f64() * f64::from(Self::SAMPLE_RATE)) as u64;
+
+    let count = due.saturating_sub(self.sample);
+
+    samples.extend(self.by_ref().take(count.try_into().unwrap()));
+  }
+
+  fn sample_rate(&self) -> u32 {
+    Self::SAMPLE_RATE
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn generate() {
+    let samples = |signal| Generator::new(signal).take(48_000).collect::<Vec<f32>>();
+
+    let impulses = samples(Signal::Impulse { frequency: 4.0 });
+    assert_eq!(impulses.iter().filter(|&&sample| sample == 1.0).count(), 4);
+    assert_eq!(impulses[12_000], 1.0);
+
+    let sine = samples(Signal::Sine { frequency: 100.0 });
+    let crossings = sine
+      .windows(2)
+      .filter(|pair| pair[0] < 0.0 && pair[1] >= 0.0)
+      .count();
+    assert_eq!(crossings, 99);
+
+    for signal in [Signal::Pink, Signal::White] {
+      let noise = samples(signal);
+      assert!(noise.iter().all(|sample| sample.abs() <= 1.0));
+      let mean = noise.iter().sum::<f32>() / noise.len() as f32;
+      assert!(mean.abs() < 0.1, "{signal:?}: {mean}");
+    }
+
+    assert_eq!(samples(Signal::White), samples(Signal::White));
+
+    let clicks = samples(Signal::Click { bpm: 240.0 });
+    assert!(clicks[12_001].abs() > 0.0);
+    assert_eq!(clicks[11_999], 0.0);
+  }
+
+  #[test]
+  fn parse() {
+    assert_eq!(
+      "sine".parse::<Signal>().unwrap(),
+      Signal::Sine { frequency: 440.0 }
+    );
+    assert_eq!(
+      "click:128".parse::<Signal>().unwrap(),
+      Signal::Click { bpm: 128.0 }
+    );
+    assert_eq!(
+      "sweep:50-5000".parse::<Signal>().unwrap(),
+      Signal::Sweep {
+        from: 50.0,
+        to: 5000.0
+      }
+    );
+    assert_eq!("pink".parse::<Signal>().unwrap(), Signal::Pink);
+    assert!(matches!(
+      "white:1".parse::<Signal>(),
+      Err(SignalError::Argument { .. })
+    ));
+    assert!(matches!(
+      "sweep:50".parse::<Signal>(),
+      Err(SignalError::Sweep { .. })
+    ));
+    for signal in [
+      "sweep:0-1000",
+      "sine:-5",
+      "click:0",
+      "impulse:0",
+      "sine:inf",
+      "sine:NaN",
+    ] {
+      assert!(
+        matches!(signal.parse::<Signal>(), Err(SignalError::Value { .. })),
+        "{signal}",
+      );
+    }
+    assert!(matches!(
+      "square".parse::<Signal>(),
+      Err(SignalError::Unknown { .. })
+    ));
+  }
+}
@@ -0,0 +1,30 @@
+use super::*;
+
+#[derive(Clone, Copy, Default)]
+pub(crate) struct Snapshot {
+  pub(crate) bands: [f32; Band::COUNT],
+  pub(crate) beat: bool,
+  pub(crate) onset: f32,
+  pub(crate) rms: f32,
+}
+
+impl Snapshot {
+  pub(crate) const LEN: usize = Band::COUNT + 3;
+
+  pub(crate) fn from_values(values: &[f32]) -> Self {
+    let (bands, rest) = values.split_at(Band::COUNT);
+    Self {
+      bands: bands.try_into().unwrap(),
+      beat: rest[0] > 0.0,
+      onset: rest[1],
+      rms: rest[2],
+    }
+  }
+
+  pub(crate) fn values(self) -> impl Iterator<Item = f32> {
+    self
+      .bands
+      .into_iter()
+      .chain([if self.beat { 1.0 } else { 0.0 }, self.onset, self.rms])
+  }
+}
@@ -0,0 +1,165 @@
+use super::*;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Song {
+  pub(crate) album: String,
+  pub(crate) artist: String,
+  pub(crate) duration: Option<Duration>,
+  pub(crate) modified: u64,
+  pub(crate) name: String,
+  pub(crate) path: PathBuf,
+  pub(crate) title: String,
+}
+
+impl Song {
+  pub(crate) fn decode(line: &str) -> Option<Self> {
+    let mut fields = line.split('\t');
+
+    let mut next = || fields.next().map(str::to_owned);
+
+    let path = next()?.into();
+    let modified = next()?.parse().ok()?;
+    let duration = next()?;
+    let duration = if duration.is_empty() {
+      None
+    } else {
+      Some(Duration::from_millis(duration.parse().ok()?))
+    };
+    let name = next()?;
+    let album = next()?;
+    let artist = next()?;
+    let title = next()?;
+
+    Some(Self {
+      album,
+      artist,
+      duration,
+      modified,
+      name,
+      path,
+      title,
+    })
+  }
+
+  pub(crate) fn encode(&self) -> Option<String> {
+    let path = self.path.to_str()?;
+
+    if path.contains(['\t', '\n', '\r']) {
+      return None;
+    }
+
+    Some(format!(
+      "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+      path,
+      self.modified,
+      self
+        .duration
+        .map(|duration| duration.as_millis().to_string())
+        .unwrap_or_default(),
+      self.name,
+      self.album,
+      self.artist,
+      self.title,
+    ))
+  }
+
+  pub(crate) fn haystacks(&self) -> [&str; 4] {
+    [&self.name, &self.title, &self.artist, &self.album]
+  }
+
+  pub(crate) fn probe(path: &Path, name: String, modified: u64) -> Option<Self> {
+    use symphonia::core::{
+      formats::FormatOptions,
+      io::MediaSourceStream,
+      meta::{MetadataOptions, MetadataRevision, StandardTagKey},
+      probe::Hint,
+    };
+
+    let file = File::open(path).ok()?;
+
+    let mut hint = Hint::new();
+
+    if let Some(extension) = path.extension().and_then(OsStr::to_str) {
+      hint.with_extension(extension);
+    }
+
+    let mut probed = symphonia::default::get_probe()
+      .format(
+        &hint,
+        MediaSourceStream::new(Box::new(file), default()),
+        &FormatOptions::default(),
+        &MetadataOptions::default(),
+      )
+      .ok()?;
+
+    let duration = probed.format.default_track().and_then(|track| {
+      let time = track
+        .codec_params
+        .time_base?
+        .calc_time(track.codec_params.n_frames?);
+      Some(Duration::from_secs(time.seconds) + Duration::from_secs_f64(time.frac))
+    });
+
+    let mut song = Self {
+      album: String::new(),
+      artist: String::new(),
+      duration,
+      modified,
+      name,
+      path: path.into(),
+      title: String::new(),
+    };
+
+    let mut read = |revision: &MetadataRevision| {
+      for tag in revision.tags() {
+        let field = match tag.std_key {
+          Some(StandardTagKey::Album) => &mut song.album,
+          Some(StandardTagKey::Artist) => &mut song.artist,
+          Some(StandardTagKey::TrackTitle) => &mut song.title,
+          _ => continue,
+        };
+
+        *field = tag.value.to_string().replace(['\t', '\n', '\r'], " ");
+      }
+    };
+
+    if let Some(metadata) = probed.metadata.get()
+      && let Some(revision) = metadata.current()
+    {
+      read(revision);
+    }
+
+    if let Some(revision) = probed.format.metadata().current() {
+      read(revision);
+    }
+
+    Some(song)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn round_trip() {
+    let song = Song {
+      album: "Album".into(),
+      artist: "Artist".into(),
+      duration: Some(Duration::from_millis(123_456)),
+      modified: 1_700_000_000,
+      name: "Artist/Album/01 Title".into(),
+      path: "/music/Artist/Album/01 Title.flac".into(),
+      title: "Title".into(),
+    };
+
+    assert_eq!(Song::decode(&song.encode().unwrap()).unwrap(), song);
+
+    let song = Song {
+      duration: None,
+      ..song
+    };
+
+    assert_eq!(Song::decode(&song.encode().unwrap()).unwrap(), song);
+  }
+}
@@ -16,26 +16,61 @@ impl State {
     self
   }
 
+  pub(crate) fn band(mut self, band: Band) -> Self {
+    self.filter.band = Some(band);
+    self
+  }
+
+  pub(crate) fn beat(mut self) -> Self {
+    self.filter.field = Field::Beat;
+    self
+  }
+
   pub(crate) fn bottom(mut self) -> Self {
     self.filter.field = Field::Bottom;
     self
   }
 
+  pub(crate) fn chroma(mut self) -> Self {
+    self.filter.field = Field::Chroma;
+    self
+  }
+
   pub(crate) fn circle(mut self) -> Self {
     self.filter.field = Field::Circle;
     self
   }
 
+  pub(crate) fn correlation(mut self) -> Self {
+    self.filter.field = Field::Correlation;
+    self
+  }
+
   pub(crate) fn db(mut self, db: i8) -> Self {
     self.db = db as f32;
     self
   }
 
+  pub(crate) fn flash(mut self) -> Self {
+    self.filter.field = Field::Flash;
+    self
+  }
+
   pub(crate) fn frequencies(mut self) -> Self {
     self.filter.field = Field::Frequencies;
     self
   }
 
+  pub(crate) fn goniometer(mut self) -> Self {
+    self.filter.field = Field::Goniometer;
+    self
+  }
+
+  pub(crate) fn hue(mut self) -> Self {
+    self.filter.hue = true;
+    self
+  }
+
   pub(crate) fn invert(mut self) -> Self {
     self.filter.color = invert_color();
     self
@@ -46,6 +81,16 @@ impl State {
     self
   }
 
+  pub(crate) fn mid_side(mut self) -> Self {
+    self.filter.field = Field::MidSide;
+    self
+  }
+
+  pub(crate) fn peaks(mut self) -> Self {
+    self.filter.field = Field::Peaks;
+    self
+  }
+
   pub(crate) fn push(mut self) -> Self {
     self.filters.push(self.filter.clone());
     self
@@ -61,6 +106,16 @@ impl State {
     self
   }
 
+  pub(crate) fn spectrogram(mut self) -> Self {
+    self.filter.field = Field::Spectrogram;
+    self
+  }
+
+  pub(crate) fn stereo(mut self) -> Self {
+    self.filter.field = Field::Stereo;
+    self
+  }
+
   pub(crate) fn text(mut self, text: Option<Text>) -> Self {
     self.text = text;
     self
@@ -78,6 +133,16 @@ impl State {
     self
   }
 
+  pub(crate) fn upcoming(mut self) -> Self {
+    self.filter.field = Field::Upcoming;
+    self
+  }
+
+  pub(crate) fn waveform(mut self) -> Self {
+    self.filter.field = Field::Waveform;
+    self
+  }
+
   pub(crate) fn x(mut self) -> Self {
     self.filter.field = Field::X;
     self
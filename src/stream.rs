@@ -6,4 +6,8 @@ pub(crate) trait Stream {
   fn drain(&mut self, samples: &mut Vec<f32>);
 
   fn sample_rate(&self) -> u32;
+
+  fn take_seek(&mut self) -> Option<usize> {
+    None
+  }
 }
@@ -1,22 +1,31 @@
 use super::*;
 
+mod analyze;
+mod learn;
 mod probe;
 mod run;
 mod shader;
+mod songs;
 
 #[derive(Default, Parser)]
 pub(crate) enum Subcommand {
+  Analyze(analyze::Analyze),
+  Learn(learn::Learn),
   Probe,
   #[default]
   Run,
   Shader,
+  Songs(songs::Songs),
 }
 
 impl Subcommand {
   pub(crate) fn run(self, options: Options) -> Result {
     match self {
+      Self::Analyze(analyze) => analyze.run(options),
+      Self::Learn(learn) => learn.run(options),
       Self::Probe => probe::run(),
       Self::Shader => shader::run(),
+      Self::Songs(songs) => songs.run(options),
       Self::Run => run::run(options),
     }
   }
@@ -0,0 +1,306 @@
+use {super::*, std::io::Write as _};
+
+#[derive(Clone, Copy, Default, ValueEnum)]
+enum Encoding {
+  #[default]
+  Csv,
+  Json,
+}
+
+#[derive(Parser)]
+pub(crate) struct Analyze {
+  #[arg(long, value_enum)]
+  format: Option<Encoding>,
+  #[arg(
+    long,
+    value_parser = clap::value_parser!(u32).range(1..),
+  )]
+  fps: Option<u32>,
+  path: PathBuf,
+}
+
+struct Number<T>(T);
+
+impl<T: Copy + Display + Into<f64>> Display for Number<T> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    // json has no representation for nan or infinity
+    if self.0.into().is_finite() {
+      write!(f, "{}", self.0)
+    } else {
+      f.write_str("null")
+    }
+  }
+}
+
+struct Row {
+  bands: [f32; Band::COUNT],
+  onset: f32,
+  raw: Snapshot,
+  rms: f32,
+  spectrum: Vec<f32>,
+  time: f64,
+}
+
+impl Analyze {
+  const FPS: u32 = 60;
+
+  fn bands(json: &mut impl io::Write, bands: [f32; Band::COUNT]) -> io::Result<()> {
+    write!(json, "{{")?;
+
+    for (i, (band, value)) in Band::iter().zip(bands).enumerate() {
+      if i > 0 {
+        write!(json, ", ")?;
+      }
+      write!(json, "{}: {}", Quoted(band.into()), Number(value))?;
+    }
+
+    write!(json, "}}")
+  }
+
+  fn csv(rows: impl Iterator<Item = Row>, csv: &mut impl io::Write) -> io::Result<()> {
+    let mut rows = rows.peekable();
+
+    write!(csv, "time,rms,onset")?;
+
+    for band in Band::iter() {
+      write!(csv, ",{}", <&str>::from(band))?;
+    }
+
+    write!(csv, ",raw_rms,raw_onset")?;
+
+    for band in Band::iter() {
+      write!(csv, ",raw_{}", <&str>::from(band))?;
+    }
+
+    for i in 0..rows.peek().map_or(0, |row| row.spectrum.len()) {
+      write!(csv, ",spectrum_{i}")?;
+    }
+
+    writeln!(csv)?;
+
+    for row in rows {
+      write!(csv, "{},{},{}", row.time, row.rms, row.onset)?;
+
+      for value in row.bands {
+        write!(csv, ",{value}")?;
+      }
+
+      write!(csv, ",{},{}", row.raw.rms, row.raw.onset)?;
+
+      for value in row.raw.bands.iter().chain(&row.spectrum) {
+        write!(csv, ",{value}")?;
+      }
+
+      writeln!(csv)?;
+    }
+
+    Ok(())
+  }
+
+  fn json(rows: impl Iterator<Item = Row>, json: &mut impl io::Write) -> io::Result<()> {
+    write!(json, "[")?;
+
+    let mut empty = true;
+
+    for row in rows {
+      if !empty {
+        write!(json, ",")?;
+      }
+
+      empty = false;
+
+      write!(json, "\n  {{\"bands\": ")?;
+
+      Self::bands(json, row.bands)?;
+
+      write!(
+        json,
+        ", \"onset\": {}, \"raw\": {{\"bands\": ",
+        Number(row.onset)
+      )?;
+
+      Self::bands(json, row.raw.bands)?;
+
+      write!(
+        json,
+        ", \"onset\": {}, \"rms\": {}}}, \"rms\": {}, \"spectrum\": [",
+        Number(row.raw.onset),
+        Number(row.raw.rms),
+        Number(row.rms),
+      )?;
+
+      for (i, value) in row.spectrum.iter().enumerate() {
+        if i > 0 {
+          write!(json, ", ")?;
+        }
+        write!(json, "{}", Number(*value))?;
+      }
+
+      write!(json, "], \"time\": {}}}", Number(row.time))?;
+    }
+
+    if !empty {
+      writeln!(json)?;
+    }
+
+    writeln!(json, "]")
+  }
+
+  fn rows<'a>(
+    samples: &'a [f32],
+    channels: u16,
+    sample_rate: u32,
+    fps: u32,
+    options: &Options,
+  ) -> impl Iterator<Item = Row> + 'a {
+    let mut analyzer = Analyzer::new(options);
+
+    let state = State::default();
+
+    let channels = usize::from(channels);
+
+    let mut start = 0;
+
+    (1..).map_while(move |frame| {
+      let end = (u64::from(sample_rate) * frame / u64::from(fps)).into_usize() * channels;
+
+      if end > samples.len() {
+        return None;
+      }
+
+      analyzer.update(
+        &mut Samples::new(
+          channels.try_into().unwrap(),
+          sample_rate,
+          samples[start..end].into(),
+        ),
+        &state,
+      );
+
+      start = end;
+
+      Some(Row {
+        bands: analyzer.bands(),
+        onset: analyzer.onset(),
+        raw: analyzer.snapshot(),
+        rms: analyzer.rms(),
+        spectrum: analyzer.frequencies().to_vec(),
+        time: (end / channels) as f64 / f64::from(sample_rate),
+      })
+    })
+  }
+
+  pub(crate) fn run(self, options: Options) -> Result {
+    let path = &self.path;
+
+    let file = File::open(path).context(error::FilesystemIo { path })?;
+
+    let decoder = Decoder::new(BufReader::new(file)).context(error::DecoderOpen { path })?;
+
+    let channels = decoder.channels();
+    let sample_rate = decoder.sample_rate();
+    let samples = decoder.collect::<Vec<f32>>();
+
+    let rows = Self::rows(
+      &samples,
+      channels,
+      sample_rate,
+      self.fps.unwrap_or(Self::FPS),
+      &options,
+    );
+
+    let mut stdout = BufWriter::new(io::stdout().lock());
+
+    match self.format.unwrap_or_default() {
+      Encoding::Csv => Self::csv(rows, &mut stdout),
+      Encoding::Json => Self::json(rows, &mut stdout),
+    }
+    .and_then(|()| stdout.flush())
+    .context(error::Stdout)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn rows() {
+    let samples = Generator::new(Signal::Sine { frequency: 1000.0 })
+      .take(48_000)
+      .collect::<Vec<f32>>();
+
+    let options = Options::default();
+
+    let rows = Analyze::rows(&samples, 1, 48_000, 60, &options).collect::<Vec<Row>>();
+
+    assert_eq!(rows.len(), 60);
+    assert_eq!(rows[59].time, 1.0);
+
+    let mut analyzer = Analyzer::new(&options);
+
+    for (row, chunk) in rows.iter().zip(samples.chunks(800)) {
+      analyzer.update(
+        &mut Samples::new(1, 48_000, chunk.into()),
+        &State::default(),
+      );
+
+      assert_eq!(row.bands, analyzer.bands());
+      assert_eq!(row.onset, analyzer.onset());
+      assert_eq!(row.rms, analyzer.rms());
+      assert_eq!(row.raw.rms, analyzer.snapshot().rms);
+    }
+
+    let last = &rows[59];
+    assert!(last.rms > 0.5);
+    assert_eq!(
+      last
+        .bands
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .unwrap()
+        .0,
+      Band::Mid as usize,
+    );
+
+    let first = || Analyze::rows(&samples, 1, 48_000, 60, &options).take(1);
+
+    let mut csv = Vec::new();
+    Analyze::csv(first(), &mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    assert!(csv.starts_with(
+      "time,rms,onset,bass,high,low-mid,mid,sub,\
+       raw_rms,raw_onset,raw_bass,raw_high,raw_low-mid,raw_mid,raw_sub,spectrum_0,"
+    ));
+    assert_eq!(csv.lines().count(), 2);
+
+    let mut json = Vec::new();
+    Analyze::json(first(), &mut json).unwrap();
+    let json = String::from_utf8(json).unwrap();
+    assert!(json.starts_with("[\n  {\"bands\": {\"bass\": "));
+    assert!(json.contains(", \"raw\": {\"bands\": {\"bass\": "));
+    assert!(json.ends_with("\"time\": 0.016666666666666666}\n]\n"));
+
+    let mut json = Vec::new();
+    Analyze::json(
+      iter::once(Row {
+        bands: [f32::NAN; Band::COUNT],
+        onset: f32::INFINITY,
+        raw: Snapshot::default(),
+        rms: 0.5,
+        spectrum: vec![f32::NEG_INFINITY, 1.0],
+        time: 0.0,
+      }),
+      &mut json,
+    )
+    .unwrap();
+    assert_eq!(
+      String::from_utf8(json).unwrap(),
+      "[\n  {\"bands\": {\"bass\": null, \"high\": null, \"low-mid\": null, \"mid\": null, \
+       \"sub\": null}, \"onset\": null, \"raw\": {\"bands\": {\"bass\": 0, \"high\": 0, \
+       \"low-mid\": 0, \"mid\": 0, \"sub\": 0}, \"onset\": 0, \"rms\": 0}, \"rms\": 0.5, \
+       \"spectrum\": [null, 1], \"time\": 0}\n]\n",
+    );
+  }
+}
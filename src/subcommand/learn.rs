@@ -0,0 +1,96 @@
+use super::*;
+
+#[derive(Parser)]
+pub(crate) struct Learn {
+  #[arg(required = true)]
+  action: Vec<String>,
+}
+
+impl Learn {
+  const FINE: Duration = Duration::from_millis(50);
+  const POLL: Duration = Duration::from_millis(10);
+
+  pub(crate) fn run(self, options: Options) -> Result {
+    let path = options.mapping.as_deref().context(error::LearnMapping)?;
+
+    let action = self.action.join(" ");
+
+    let action = action
+      .parse::<Action>()
+      .context(error::LearnAction { action: &action })?;
+
+    let mut text = match fs::read_to_string(path) {
+      Ok(text) => text,
+      Err(err) if err.kind() == io::ErrorKind::NotFound => Mapping::DEFAULT.into(),
+      Err(err) => return Err(error::FilesystemIo { path }.into_error(err)),
+    };
+
+    let mut mapping = Mapping::parse(path, &text)?;
+
+    let mut hub = Hub::new(&mapping)?;
+
+    eprintln!("press or move a control to bind `{action}`");
+
+    let mut message = loop {
+      if let Some(message) = hub.messages().lock().unwrap().pop_front() {
+        break message;
+      }
+
+      std::thread::sleep(Self::POLL);
+    };
+
+    // a 14-bit controller sends its least significant byte right after the
+    // most significant one, so wait briefly to see if this is one
+    if let Event::ControlChange { value: msb } = message.event
+      && message.number < Controllers::LSB
+    {
+      let deadline = Instant::now() + Self::FINE;
+
+      while Instant::now() < deadline {
+        let lsb = hub
+          .messages()
+          .lock()
+          .unwrap()
+          .drain(..)
+          .find_map(|fine| match fine.event {
+            Event::ControlChange { value }
+              if fine.channel == message.channel
+                && fine.number == message.number + Controllers::LSB
+                && fine.port == message.port =>
+            {
+              Some(value)
+            }
+            _ => None,
+          });
+
+        if let Some(lsb) = lsb {
+          message.event = Event::ControlChange14 {
+            value: Controllers::combine(msb, lsb),
+          };
+          break;
+        }
+
+        std::thread::sleep(Self::POLL);
+      }
+    }
+
+    let learned = mapping.learn(action.clone(), &message)?;
+
+    if learned.is_empty() {
+      eprintln!("`{action}` is already bound to that control");
+      return Ok(());
+    }
+
+    if !text.is_empty() && !text.ends_with('\n') {
+      text.push('\n');
+    }
+
+    text.push_str(&learned);
+
+    fs::write(path, text).context(error::FilesystemIo { path })?;
+
+    eprint!("{learned}");
+
+    Ok(())
+  }
+}
@@ -0,0 +1,135 @@
+use {
+  super::*,
+  tabled::{Table, Tabled, settings::style::Style},
+};
+
+#[derive(Parser)]
+pub(crate) struct Songs {
+  #[arg(long, conflicts_with = "pick")]
+  json: bool,
+  #[arg(long)]
+  pick: bool,
+  song: String,
+}
+
+#[derive(Tabled)]
+#[tabled(rename_all = "Upper Title Case")]
+#[allow(clippy::arbitrary_source_item_ordering)]
+struct Row {
+  number: usize,
+  artist: String,
+  album: String,
+  title: String,
+  duration: String,
+  path: String,
+}
+
+impl Songs {
+  fn json(songs: &[&Song]) -> String {
+    let mut json = String::from("[");
+
+    for (i, song) in songs.iter().enumerate() {
+      if i > 0 {
+        json.push(',');
+      }
+
+      write!(
+        json,
+        "\n  {{\"album\": {}, \"artist\": {}, \"duration\": {}, \"path\": {}, \"title\": {}}}",
+        Quoted(&song.album),
+        Quoted(&song.artist),
+        song
+          .duration
+          .map_or("null".into(), |duration| duration.as_secs_f64().to_string()),
+        Quoted(&song.path.to_string_lossy()),
+        Quoted(&song.title),
+      )
+      .unwrap();
+    }
+
+    if !songs.is_empty() {
+      json.push('\n');
+    }
+
+    json.push(']');
+
+    json
+  }
+
+  pub(crate) fn run(self, options: Options) -> Result {
+    let library = Library::load(&options)?;
+
+    let songs = library.search(&self.song)?;
+
+    if self.json {
+      println!("{}", Self::json(&songs));
+      return Ok(());
+    }
+
+    println!(
+      "{}",
+      Table::new(songs.iter().enumerate().map(|(i, song)| {
+        Row {
+          number: i + 1,
+          artist: song.artist.clone(),
+          album: song.album.clone(),
+          title: song.title.clone(),
+          duration: song
+            .duration
+            .map(|duration| Timestamp(duration).to_string())
+            .unwrap_or_default(),
+          path: song.path.display().to_string(),
+        }
+      }))
+      .with(Style::modern())
+    );
+
+    if !self.pick || songs.is_empty() {
+      return Ok(());
+    }
+
+    eprint!("song number: ");
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).context(error::Stdin)?;
+    let input = input.trim();
+
+    let song = input
+      .parse::<usize>()
+      .ok()
+      .and_then(|number| songs.get(number.checked_sub(1)?))
+      .context(error::SongPick { input })?;
+
+    run::run(Options {
+      song: None,
+      track: Some(song.path.clone()),
+      ..options
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn json() {
+    assert_eq!(Songs::json(&[]), "[]");
+
+    let song = Song {
+      album: "Album".into(),
+      artist: "A \"B\"".into(),
+      duration: Some(Duration::from_millis(1500)),
+      modified: 0,
+      name: "a".into(),
+      path: "/music/a.flac".into(),
+      title: "Title".into(),
+    };
+
+    assert_eq!(
+      Songs::json(&[&song]),
+      "[\n  {\"album\": \"Album\", \"artist\": \"A \\\"B\\\"\", \"duration\": 1.5, \
+       \"path\": \"/music/a.flac\", \"title\": \"Title\"}\n]",
+    );
+  }
+}
@@ -0,0 +1,10 @@
+use super::*;
+
+pub(crate) struct Timestamp(pub(crate) Duration);
+
+impl Display for Timestamp {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let seconds = self.0.as_secs();
+    write!(f, "{}:{:02}", seconds / 60, seconds % 60)
+  }
+}
@@ -4,53 +4,129 @@ use super::*;
 pub(crate) struct Track(Arc<RwLock<Inner>>);
 
 struct Inner {
+  ahead: VecDeque<f32>,
   buffer: Vec<f32>,
-  decoder: Decoder<BufReader<File>>,
   done: bool,
+  lookahead: usize,
+  looping: Option<(Duration, Duration)>,
+  played: u64,
+  seek: Option<usize>,
+  source: Box<dyn Source + Send + Sync>,
+}
+
+impl Inner {
+  fn position(&self) -> Duration {
+    Duration::from_secs_f64(
+      (self.played / u64::from(self.source.channels())) as f64
+        / f64::from(self.source.sample_rate()),
+    )
+  }
+
+  #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+  fn samples(&self, position: Duration) -> u64 {
+    (position.as_secs_f64() * f64::from(self.source.sample_rate())) as u64
+      * u64::from(self.source.channels())
+  }
+
+  fn seek(&mut self, position: Duration) -> Result {
+    let position = self
+      .source
+      .total_duration()
+      .map_or(position, |duration| position.min(duration));
+
+    self.source.try_seek(position).context(error::Seek)?;
+
+    self.ahead.clear();
+    self.buffer.clear();
+    self.done = false;
+    self.played = self.samples(position);
+    self.seek = Some((self.played / u64::from(self.source.channels())).into_usize());
+
+    Ok(())
+  }
 }
 
 impl Track {
-  pub(crate) fn new(path: &Path) -> Result<Self> {
+  pub(crate) fn duration(&self) -> Option<Duration> {
+    self.read().source.total_duration()
+  }
+
+  pub(crate) fn looping(&self) -> Option<(Duration, Duration)> {
+    self.read().looping
+  }
+
+  pub(crate) fn new(source: impl Source + Send + Sync + 'static, lookahead: Duration) -> Self {
+    let lookahead = (lookahead.as_secs_f32() * source.sample_rate() as f32).into_usize()
+      * usize::from(source.channels());
+
+    Self(Arc::new(RwLock::new(Inner {
+      ahead: VecDeque::new(),
+      buffer: Vec::new(),
+      done: false,
+      lookahead,
+      looping: None,
+      played: 0,
+      seek: None,
+      source: Box::new(source),
+    })))
+  }
+
+  pub(crate) fn open(path: &Path, lookahead: Duration) -> Result<Self> {
     let file = File::open(path).context(error::FilesystemIo { path })?;
     let reader = BufReader::new(file);
     let source = Decoder::new(reader).context(error::DecoderOpen { path })?;
-    Ok(Self(Arc::new(RwLock::new(Inner {
-      buffer: Vec::new(),
-      decoder: source,
-      done: false,
-    }))))
+    Ok(Self::new(source, lookahead))
+  }
+
+  pub(crate) fn position(&self) -> Duration {
+    self.read().position()
   }
 
   fn read(&self) -> RwLockReadGuard<Inner> {
     self.0.read().unwrap()
   }
 
-  fn write(&mut self) -> RwLockWriteGuard<Inner> {
+  pub(crate) fn seek(&self, position: Duration) -> Result {
+    self.write().seek(position)
+  }
+
+  pub(crate) fn set_looping(&self, looping: Option<(Duration, Duration)>) {
+    self.write().looping = looping;
+  }
+
+  fn write(&self) -> RwLockWriteGuard<Inner> {
     self.0.write().unwrap()
   }
 }
 
 impl Source for Track {
   fn channels(&self) -> u16 {
-    self.read().decoder.channels()
+    self.read().source.channels()
   }
 
   fn current_span_len(&self) -> Option<usize> {
-    self.read().decoder.current_span_len()
+    self.read().source.current_span_len()
   }
 
   fn sample_rate(&self) -> u32 {
-    self.read().decoder.sample_rate()
+    self.read().source.sample_rate()
+  }
+
+  fn total_duration(&self) -> Option<Duration> {
+    self.read().source.total_duration()
   }
 
-  fn total_duration(&self) -> Option<std::time::Duration> {
-    self.read().decoder.total_duration()
+  fn try_seek(&mut self, position: Duration) -> Result<(), SeekError> {
+    self
+      .write()
+      .seek(position)
+      .map_err(|err| SeekError::Other(Box::new(err)))
   }
 }
 
 impl Stream for Track {
   fn channels(&self) -> u16 {
-    self.read().decoder.channels()
+    self.read().source.channels()
   }
 
   fn done(&self) -> bool {
@@ -63,7 +139,11 @@ impl Stream for Track {
   }
 
   fn sample_rate(&self) -> u32 {
-    self.read().decoder.sample_rate()
+    self.read().source.sample_rate()
+  }
+
+  fn take_seek(&mut self) -> Option<usize> {
+    self.write().seek.take()
   }
 }
 
@@ -71,17 +151,93 @@ impl Iterator for Track {
   type Item = f32;
 
   fn next(&mut self) -> Option<f32> {
-    let mut inner = self.write();
+    let inner = &mut *self.write();
+
+    if let Some((start, end)) = inner.looping
+      && inner.played >= inner.samples(end)
+      && inner
+        .played
+        .is_multiple_of(u64::from(inner.source.channels()))
+      && let Err(err) = inner.seek(start)
+    {
+      log::warn!("failed to loop: {err}");
+      inner.looping = None;
+    }
+
+    while !inner.done && inner.ahead.len() <= inner.lookahead {
+      let Some(sample) = inner.source.next() else {
+        inner.done = true;
+        break;
+      };
 
-    let Some(sample) = inner.decoder.next() else {
-      inner.done = true;
-      return None;
-    };
+      inner.ahead.push_back(sample);
+      inner.buffer.push(sample);
+    }
 
-    let sample = sample.to_sample();
+    let sample = inner.ahead.pop_front()?;
 
-    inner.buffer.push(sample);
+    inner.played += 1;
 
     Some(sample)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use {super::*, rodio::buffer::SamplesBuffer};
+
+  #[test]
+  fn lookahead() {
+    let mut track = Track::new(
+      SamplesBuffer::new(1, 10, (0..20).map(|i| i as f32).collect::<Vec<f32>>()),
+      Duration::from_millis(500),
+    );
+
+    assert_eq!(track.next(), Some(0.0));
+
+    let mut samples = Vec::new();
+    track.drain(&mut samples);
+    assert_eq!(samples, [0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+
+    assert_eq!(track.by_ref().count(), 19);
+
+    assert!(!track.done());
+
+    track.drain(&mut samples);
+    assert_eq!(samples.len(), 20);
+
+    assert!(track.done());
+  }
+
+  #[test]
+  fn seek() {
+    let mut track = Track::new(
+      SamplesBuffer::new(1, 10, (0..20).map(|i| i as f32).collect::<Vec<f32>>()),
+      Duration::ZERO,
+    );
+
+    assert_eq!(track.next(), Some(0.0));
+    assert_eq!(track.take_seek(), None);
+
+    track.seek(Duration::from_secs(1)).unwrap();
+
+    assert_eq!(track.take_seek(), Some(10));
+    assert_eq!(track.position(), Duration::from_secs(1));
+    assert_eq!(track.next(), Some(10.0));
+
+    let mut samples = Vec::new();
+    track.drain(&mut samples);
+    assert_eq!(samples, [10.0]);
+
+    track.set_looping(Some((
+      Duration::from_millis(1500),
+      Duration::from_millis(1700),
+    )));
+    track.seek(Duration::from_millis(1500)).unwrap();
+
+    assert_eq!(
+      track.by_ref().take(5).collect::<Vec<f32>>(),
+      [15.0, 16.0, 15.0, 16.0, 15.0],
+    );
+  }
+}
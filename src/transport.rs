@@ -0,0 +1,68 @@
+use super::*;
+
+pub(crate) struct Transport {
+  cue: Duration,
+  start: Option<Duration>,
+  step: Duration,
+  track: Track,
+}
+
+impl Transport {
+  pub(crate) fn back(&self) -> Result {
+    self
+      .track
+      .seek(self.track.position().saturating_sub(self.step))
+  }
+
+  pub(crate) fn cue(&mut self) {
+    self.cue = self.track.position();
+  }
+
+  pub(crate) fn forward(&self) -> Result {
+    self.track.seek(self.track.position() + self.step)
+  }
+
+  pub(crate) fn jump(&self) -> Result {
+    self.track.seek(self.cue)
+  }
+
+  pub(crate) fn loop_point(&mut self) {
+    if self.track.looping().is_some() {
+      self.track.set_looping(None);
+      return;
+    }
+
+    let position = self.track.position();
+
+    match self.start.take() {
+      Some(start) if start < position => self.track.set_looping(Some((start, position))),
+      _ => self.start = Some(position),
+    }
+  }
+
+  pub(crate) fn new(track: Track, step: Duration) -> Self {
+    Self {
+      cue: Duration::ZERO,
+      start: None,
+      step,
+      track,
+    }
+  }
+
+  pub(crate) fn playhead(&self, paused: bool) -> Playhead {
+    Playhead {
+      duration: self.track.duration(),
+      looping: self.track.looping().is_some(),
+      paused,
+      position: self.track.position(),
+    }
+  }
+
+  pub(crate) fn remaining(&self) -> Option<Duration> {
+    Some(self.track.duration()?.saturating_sub(self.track.position()))
+  }
+
+  pub(crate) fn restart(&self) -> Result {
+    self.track.seek(Duration::ZERO)
+  }
+}
@@ -3,8 +3,14 @@ use super::*;
 #[derive(Default)]
 pub(crate) struct Uniforms {
   pub(crate) back_read: bool,
+  pub(crate) band: Option<Band>,
+  pub(crate) bands: [f32; Band::COUNT],
+  pub(crate) beat: f32,
+  pub(crate) channel_range: f32,
+  pub(crate) chroma: [f32; Chroma::PITCH_CLASSES],
   pub(crate) color: Mat4f,
   pub(crate) coordinates: bool,
+  pub(crate) correlation: f32,
   pub(crate) field: Field,
   pub(crate) filters: u32,
   pub(crate) fit: bool,
@@ -12,14 +18,21 @@ pub(crate) struct Uniforms {
   pub(crate) front_offset: Vec2f,
   pub(crate) front_read: bool,
   pub(crate) gain: f32,
+  pub(crate) history: u32,
+  pub(crate) hue: bool,
   pub(crate) index: u32,
   pub(crate) offset: Vec2f,
+  pub(crate) onset: f32,
+  pub(crate) pitch: Option<u32>,
   pub(crate) position: Mat3f,
   pub(crate) repeat: bool,
   pub(crate) resolution: Vec2f,
   pub(crate) rms: f32,
   pub(crate) sample_range: f32,
+  pub(crate) source: u32,
+  pub(crate) spectrum_range: f32,
   pub(crate) tiling: u32,
+  pub(crate) upcoming: f32,
   pub(crate) wrap: bool,
 }
 
@@ -28,8 +41,14 @@ impl Uniforms {
     let mut i = 0;
     let mut a = 0;
     self.back_read.write(dst, &mut i, &mut a);
+    self.band.write(dst, &mut i, &mut a);
+    self.bands.write(dst, &mut i, &mut a);
+    self.beat.write(dst, &mut i, &mut a);
+    self.channel_range.write(dst, &mut i, &mut a);
+    self.chroma.write(dst, &mut i, &mut a);
     self.color.write(dst, &mut i, &mut a);
     self.coordinates.write(dst, &mut i, &mut a);
+    self.correlation.write(dst, &mut i, &mut a);
     self.field.write(dst, &mut i, &mut a);
     self.filters.write(dst, &mut i, &mut a);
     self.fit.write(dst, &mut i, &mut a);
@@ -37,14 +56,21 @@ impl Uniforms {
     self.front_offset.write(dst, &mut i, &mut a);
     self.front_read.write(dst, &mut i, &mut a);
     self.gain.write(dst, &mut i, &mut a);
+    self.history.write(dst, &mut i, &mut a);
+    self.hue.write(dst, &mut i, &mut a);
     self.index.write(dst, &mut i, &mut a);
     self.offset.write(dst, &mut i, &mut a);
+    self.onset.write(dst, &mut i, &mut a);
+    self.pitch.write(dst, &mut i, &mut a);
     self.position.write(dst, &mut i, &mut a);
     self.repeat.write(dst, &mut i, &mut a);
     self.resolution.write(dst, &mut i, &mut a);
     self.rms.write(dst, &mut i, &mut a);
     self.sample_range.write(dst, &mut i, &mut a);
+    self.source.write(dst, &mut i, &mut a);
+    self.spectrum_range.write(dst, &mut i, &mut a);
     self.tiling.write(dst, &mut i, &mut a);
+    self.upcoming.write(dst, &mut i, &mut a);
     self.wrap.write(dst, &mut i, &mut a);
     pad(i, a)
   }
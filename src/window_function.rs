@@ -0,0 +1,24 @@
+use super::*;
+
+#[derive(Clone, Copy, Default, ValueEnum)]
+pub(crate) enum WindowFunction {
+  Blackman,
+  #[default]
+  Hann,
+  Rectangular,
+}
+
+impl WindowFunction {
+  pub(crate) fn coefficients(self, size: usize) -> Vec<f32> {
+    (0..size)
+      .map(|n| {
+        let x = TAU * n as f32 / size as f32;
+        match self {
+          Self::Blackman => 0.42 - 0.5 * x.cos() + 0.08 * (2.0 * x).cos(),
+          Self::Hann => 0.5 - 0.5 * x.cos(),
+          Self::Rectangular => 1.0,
+        }
+      })
+      .collect()
+  }
+}
@@ -8,7 +8,7 @@ var filtering_sampler: sampler;
 
 @group(0)
 @binding(2)
-var frequencies: texture_1d<f32>;
+var frequencies: texture_2d<f32>;
 
 @group(0)
 @binding(3)
@@ -16,17 +16,48 @@ var front: texture_2d<f32>;
 
 @group(0)
 @binding(4)
-var non_filtering_sampler: sampler;
+var left: texture_2d<f32>;
 
 @group(0)
 @binding(5)
-var samples: texture_1d<f32>;
+var mid: texture_2d<f32>;
 
 @group(0)
 @binding(6)
+var non_filtering_sampler: sampler;
+
+@group(0)
+@binding(7)
+var peaks: texture_2d<f32>;
+
+@group(0)
+@binding(8)
+var right: texture_2d<f32>;
+
+@group(0)
+@binding(9)
+var samples: texture_2d<f32>;
+
+@group(0)
+@binding(10)
+var side: texture_2d<f32>;
+
+@group(0)
+@binding(11)
+var spectrogram: texture_2d<f32>;
+
+@group(0)
+@binding(12)
 var<uniform> uniforms: Uniforms;
 
+@group(0)
+@binding(13)
+var waveform: texture_2d<f32>;
+
+const BANDS: u32 = {{ Band::COUNT }};
 const ERROR = vec4(0.0, 1.0, 0.0, 1.0);
+const HISTORY: u32 = {{ History::ROWS }};
+const PITCH_CLASSES: u32 = {{ Chroma::PITCH_CLASSES }};
 const TRANSPARENT = vec4(0.0, 0.0, 0.0, 0.0);
 
 %% for field in Field::iter() {
@@ -41,8 +72,14 @@ const VERTICES = array(
 
 struct Uniforms {
   back_read: u32,
+  band: u32,
+  bands: array<vec4f, {{ Band::COUNT.div_ceil(4) }}>,
+  beat: f32,
+  channel_range: f32,
+  chroma: array<vec4f, {{ Chroma::PITCH_CLASSES.div_ceil(4) }}>,
   color: mat4x4f,
   coordinates: u32,
+  correlation: f32,
   field: u32,
   filters: u32,
   fit: u32,
@@ -50,57 +87,163 @@ struct Uniforms {
   front_offset: vec2f,
   front_read: u32,
   gain: f32,
+  history: u32,
+  hue: u32,
   index: u32,
   offset: vec2f,
+  onset: f32,
+  pitch: u32,
   position: mat3x3f,
   repeat: u32,
   resolution: vec2f,
   rms: f32,
   sample_range: f32,
+  source: u32,
+  spectrum_range: f32,
   tiling: u32,
+  upcoming: f32,
   wrap: u32,
 }
 
+fn band(i: u32) -> f32 {
+  return uniforms.bands[i / 4][i % 4];
+}
+
+fn channels(upper: texture_2d<f32>, lower: texture_2d<f32>, p: vec2f) -> bool {
+  // spectrum rows follow each source's sample row
+  let x = (p.x + 1) * 0.5 * uniforms.spectrum_range;
+  let column = u32(x * f32(textureDimensions(upper).x));
+  let row = uniforms.source * 2 + 1;
+  if p.y < 0 {
+    return textureLoad(upper, vec2(column, row), 0).x * uniforms.gain > -p.y;
+  }
+  return textureLoad(lower, vec2(column, row), 0).x * uniforms.gain > p.y;
+}
+
+fn chroma(i: u32) -> f32 {
+  return uniforms.chroma[i / 4][i % 4];
+}
+
 fn coefficient() -> f32 {
-  return 1 + uniforms.rms / 10 * uniforms.gain;
+  var level = uniforms.rms;
+  if uniforms.band < BANDS {
+    level = band(uniforms.band);
+  }
+  return 1 + level / 10 * uniforms.gain;
 }
 
 fn field_all(p: vec2f) -> bool {
   return true;
 }
 
+fn field_beat(p: vec2f) -> bool {
+  return length(p) < uniforms.beat;
+}
+
 fn field_bottom(p: vec2f) -> bool {
   return field_top(-p);
 }
 
+fn field_chroma(p: vec2f) -> bool {
+  let i = min(u32((p.x + 1) * 0.5 * f32(PITCH_CLASSES)), PITCH_CLASSES - 1);
+  return chroma(i) * uniforms.gain > (-p.y + 1) * 0.5;
+}
+
 fn field_circle(p: vec2f) -> bool {
   return length(p) < 0.5 * coefficient();
 }
 
+fn field_correlation(p: vec2f) -> bool {
+  return abs(p.y) < 0.1 && p.x * (p.x - uniforms.correlation) <= 0;
+}
+
+fn field_flash(p: vec2f) -> bool {
+  return coefficient() > 1.5;
+}
+
 fn field_frequencies(p: vec2f) -> bool {
   let x = (p.x + 1) * 0.5 * uniforms.frequency_range;
-  let level = textureSample(frequencies, non_filtering_sampler, x).x * uniforms.gain;
+  let level = textureSample(frequencies, non_filtering_sampler, vec2(x, source())).x * uniforms.gain;
   return level > (-p.y + 1) * 0.5;
 }
 
+fn field_goniometer(p: vec2f) -> bool {
+  let count = u32(uniforms.channel_range * f32(textureDimensions(mid).x));
+  for (var i = count - min(count, 256u); i < count; i++) {
+    let x = textureLoad(side, vec2(i, uniforms.source * 2), 0).x;
+    let y = textureLoad(mid, vec2(i, uniforms.source * 2), 0).x;
+    if distance(p, vec2(-x, -y) * uniforms.gain) < 0.01 {
+      return true;
+    }
+  }
+  return false;
+}
+
+fn field_midside(p: vec2f) -> bool {
+  return channels(mid, side, p);
+}
+
 fn field_none(p: vec2f) -> bool {
   return false;
 }
 
+fn field_onset(p: vec2f) -> bool {
+  return length(p) < uniforms.onset * uniforms.gain;
+}
+
+fn field_peaks(p: vec2f) -> bool {
+  let x = (p.x + 1) * 0.5 * uniforms.frequency_range;
+  let level = textureSample(peaks, non_filtering_sampler, vec2(x, source())).x * uniforms.gain;
+  return abs(level - (-p.y + 1) * 0.5) < 0.01;
+}
+
 fn field_samples(p: vec2f) -> bool {
   let x = (p.x + 1) * 0.5 * uniforms.sample_range;
-  let level = textureSample(samples, non_filtering_sampler, x).x * uniforms.gain;
+  let level = textureSample(samples, non_filtering_sampler, vec2(x, source())).x * uniforms.gain;
   return level < p.y;
 }
 
+fn field_spectrogram(p: vec2f) -> bool {
+  let x = (p.x + 1) * 0.5 * uniforms.frequency_range;
+  let column = u32(x * f32(textureDimensions(spectrogram).x));
+  let level = textureLoad(spectrogram, vec2(column, history((p.y + 1) * 0.5)), 0).x;
+  return level * uniforms.gain > 0.5;
+}
+
+fn field_stereo(p: vec2f) -> bool {
+  return channels(left, right, p);
+}
+
 fn field_top(p: vec2f) -> bool {
   return p.y + 1 < coefficient();
 }
 
+fn field_upcoming(p: vec2f) -> bool {
+  // ring where the circle field will be at the loudest point of the next half second
+  let radius = 0.5 * (1 + uniforms.upcoming / 10 * uniforms.gain);
+  return abs(length(p) - radius) < 0.02;
+}
+
+fn field_waveform(p: vec2f) -> bool {
+  let row = history((1 - p.x) * 0.5);
+  let low = textureLoad(waveform, vec2(0u, row), 0).x * uniforms.gain;
+  let high = textureLoad(waveform, vec2(1u, row), 0).x * uniforms.gain;
+  return -p.y >= low && -p.y <= high;
+}
+
 fn field_x(p: vec2f) -> bool {
   return abs(abs(p.x) - abs(p.y)) < 0.2 * coefficient();
 }
 
+fn history(age: f32) -> u32 {
+  let rows = u32(clamp(age, 0.0, 1.0) * f32(HISTORY - 1));
+  return uniforms.source * HISTORY + (uniforms.history + HISTORY - rows) % HISTORY;
+}
+
+fn hue(h: f32) -> vec3f {
+  return clamp(abs(fract(h + vec3(0.0, 2.0 / 3.0, 1.0 / 3.0)) * 6 - 3) - 1, vec3(0.0), vec3(1.0));
+}
+
 fn invert(color: vec4f) -> vec4f {
   return vec4((color.xyx - 1) * -1, 1);
 }
@@ -109,6 +252,10 @@ fn read(uv: vec2f) -> bool {
   return bool(uniforms.repeat) || all(uv >= vec2(0.0, 0.0)) && all(uv <= vec2(1.0, 1.0));
 }
 
+fn source() -> f32 {
+  return (f32(uniforms.source) + 0.5) / f32(textureDimensions(samples).y);
+}
+
 @vertex
 fn vertex(@builtin(vertex_index) i: u32) -> @builtin(position) vec4f {
   return vec4(VERTICES[i], 0, 1);
@@ -208,6 +355,12 @@ fn fragment(@builtin(position) position: vec4f) -> @location(0) vec4f {
     // convert back to rgb
     let color = (transformed + 1) / 2;
 
+    if bool(uniforms.hue) && uniforms.pitch < PITCH_CLASSES {
+      // tint by dominant pitch class
+      let value = max(color.r, max(color.g, color.b));
+      return vec4(hue(f32(uniforms.pitch) / f32(PITCH_CLASSES)) * value, 1.0);
+    }
+
     return color;
   } else {
     return input;